@@ -1,7 +1,7 @@
 use std::hint::black_box;
 
 use criterion::{criterion_group, criterion_main, Criterion};
-use torchbearer::bresenham::{BresenhamCircle, BresenhamLine, ThickBresenhamCircle};
+use torchbearer::bresenham::{BresenhamCircle, BresenhamLine, FilledCircle, ThickBresenhamCircle};
 
 const CIRCLE_RADIUS: i32 = 60;
 
@@ -35,10 +35,31 @@ pub fn thick_bresenham_circle(c: &mut Criterion) {
         });
 }
 
+pub fn filled_circle(c: &mut Criterion) {
+    let mut group = c.benchmark_group("filled_circle");
+    group.bench_function("spans", |b| {
+        b.iter(|| {
+            let disc = FilledCircle::new(black_box((0, 0)), black_box(CIRCLE_RADIUS));
+            let _vec = disc.collect::<Vec<_>>();
+        });
+    });
+    group.bench_function("bounding_box", |b| {
+        b.iter(|| {
+            let radius = black_box(CIRCLE_RADIUS);
+            let _vec = (-radius..=radius)
+                .flat_map(|y| (-radius..=radius).map(move |x| (x, y)))
+                .filter(|&(x, y)| x * x + y * y <= radius * radius)
+                .collect::<Vec<_>>();
+        });
+    });
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bresenham_line,
     bresenham_circle,
-    thick_bresenham_circle
+    thick_bresenham_circle,
+    filled_circle
 );
 criterion_main!(benches);
@@ -10,6 +10,7 @@ const HEIGHT: i32 = 45;
 const POSITION_X: i32 = 22;
 const POSITION_Y: i32 = 22;
 const RADIUS: i32 = 12;
+const SMALL_RADIUS: i32 = 2;
 const RANDOM_WALLS: i32 = 10;
 
 pub struct SampleMap {
@@ -162,6 +163,45 @@ pub fn bracket_fov_random_walls(group: &mut BenchmarkGroup<WallTime>) {
     });
 }
 
+pub fn torchbearer_fov_small_radius(group: &mut BenchmarkGroup<WallTime>) {
+    let map = SampleMap::new(WIDTH, HEIGHT).randomize_walls();
+
+    group.bench_function("torchbearer", |bencher| {
+        bencher
+            .iter(|| torchbearer::fov::field_of_view(&map, (POSITION_X, POSITION_Y), SMALL_RADIUS));
+    });
+}
+
+pub fn tcod_fov_small_radius(group: &mut BenchmarkGroup<WallTime>) {
+    let mut map: TcodMap = SampleMap::new(WIDTH, HEIGHT).randomize_walls().into();
+
+    group.bench_function("tcod", |bencher| {
+        bencher.iter(|| {
+            map.compute_fov(
+                POSITION_X,
+                POSITION_Y,
+                SMALL_RADIUS,
+                true,
+                tcod::map::FovAlgorithm::Basic,
+            )
+        });
+    });
+}
+
+pub fn bracket_fov_small_radius(group: &mut BenchmarkGroup<WallTime>) {
+    let map = SampleMap::new(WIDTH, HEIGHT).randomize_walls();
+
+    group.bench_function("bracket", |bencher| {
+        bencher.iter(|| {
+            bracket_pathfinding::prelude::field_of_view(
+                (POSITION_X, POSITION_Y).into(),
+                SMALL_RADIUS,
+                &map,
+            )
+        });
+    });
+}
+
 pub fn fov_no_walls(c: &mut Criterion) {
     let mut group = c.benchmark_group("fov_no_walls");
     torchbearer_fov_no_walls(&mut group);
@@ -176,5 +216,12 @@ pub fn fov_random_walls(c: &mut Criterion) {
     bracket_fov_random_walls(&mut group);
 }
 
-criterion_group!(benches, fov_no_walls, fov_random_walls);
+pub fn fov_small_radius(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fov_small_radius");
+    torchbearer_fov_small_radius(&mut group);
+    tcod_fov_small_radius(&mut group);
+    bracket_fov_small_radius(&mut group);
+}
+
+criterion_group!(benches, fov_no_walls, fov_random_walls, fov_small_radius);
 criterion_main!(benches);
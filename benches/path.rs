@@ -5,12 +5,17 @@ use criterion::{
 use tcod::Map as TcodMap;
 use torchbearer::{
     bresenham::BresenhamLine,
-    path::{astar_path, astar_path_fourwaygrid, FourWayGridGraph, PathMap},
+    path::{
+        astar_path, astar_path_fourwaygrid, astar_path_weighted, is_reachable_fourwaygrid,
+        FourWayGridGraph, PathMap,
+    },
     Point,
 };
 
 const WIDTH: i32 = 20;
 const HEIGHT: i32 = 20;
+const LARGE_WIDTH: i32 = 200;
+const LARGE_HEIGHT: i32 = 200;
 
 struct TestMap {
     width: i32,
@@ -36,6 +41,13 @@ impl TestMap {
         self
     }
 
+    /// A wall cutting the map in two halves, to make the destination unreachable.
+    fn with_split(mut self) -> Self {
+        let x = self.width / 2;
+        self.build_wall((x, 0), (x, self.height - 1));
+        self
+    }
+
     fn build_wall(&mut self, from: Point, to: Point) {
         let bresenham = BresenhamLine::new(from, to);
         for (x, y) in bresenham {
@@ -175,5 +187,48 @@ pub fn astar(c: &mut Criterion) {
     tcod_astar(&mut group);
 }
 
-criterion_group!(benches, astar,);
+pub fn torchbearer_astar_weighted(group: &mut BenchmarkGroup<WallTime>, epsilon: f32) {
+    let map = TestMap::new(WIDTH, HEIGHT).with_walls();
+    let graph = FourWayGridGraph::new(&map);
+    let from = (1 + 4 * WIDTH) as usize;
+    let to = (15 + 8 * WIDTH) as usize;
+
+    group.bench_function(format!("epsilon_{}", epsilon), |bencher| {
+        bencher.iter(|| astar_path_weighted(&graph, from, to, epsilon));
+    });
+}
+
+pub fn weighted_astar(c: &mut Criterion) {
+    let mut group = c.benchmark_group("weighted_astar");
+    torchbearer_astar_weighted(&mut group, 1.0);
+    torchbearer_astar_weighted(&mut group, 2.0);
+}
+
+pub fn torchbearer_astar_unreachable(group: &mut BenchmarkGroup<WallTime>) {
+    let map = TestMap::new(LARGE_WIDTH, LARGE_HEIGHT).with_split();
+    let from = (1, 1);
+    let to = (LARGE_WIDTH - 2, LARGE_HEIGHT - 2);
+
+    group.bench_function("astar_path_fourwaygrid", |bencher| {
+        bencher.iter(|| astar_path_fourwaygrid(&map, from, to).is_some());
+    });
+}
+
+pub fn torchbearer_is_reachable_unreachable(group: &mut BenchmarkGroup<WallTime>) {
+    let map = TestMap::new(LARGE_WIDTH, LARGE_HEIGHT).with_split();
+    let from = (1, 1);
+    let to = (LARGE_WIDTH - 2, LARGE_HEIGHT - 2);
+
+    group.bench_function("is_reachable_fourwaygrid", |bencher| {
+        bencher.iter(|| is_reachable_fourwaygrid(&map, from, to));
+    });
+}
+
+pub fn reachability(c: &mut Criterion) {
+    let mut group = c.benchmark_group("reachability");
+    torchbearer_astar_unreachable(&mut group);
+    torchbearer_is_reachable_unreachable(&mut group);
+}
+
+criterion_group!(benches, astar, reachability, weighted_astar);
 criterion_main!(benches);
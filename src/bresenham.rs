@@ -1,6 +1,10 @@
 //! Collection of bresenham implementation of lines, circles, … as Iterable.
 
-use core::iter::Iterator;
+use core::{
+    f32::consts::TAU,
+    iter::Iterator,
+    ops::{Add, Sub},
+};
 
 use crate::Point;
 
@@ -46,12 +50,33 @@ pub struct BresenhamLine {
     octant: Octant,
 }
 
-struct Octant(u8);
+/// One of the eight octants around a point, used to fold any line into the first octant,
+/// where it goes east and south-east only, and unfold it back. This is how [`BresenhamLine`]
+/// only needs to handle one case.
+///
+/// # Example
+///
+/// ```
+/// use torchbearer::bresenham::Octant;
+///
+/// let (start, end) = ((2, 2), (-1, 6));
+/// let octant = Octant::from_points(start, end);
+///
+/// // In the first octant, the line goes further along x than along y, and both grow.
+/// let (dx, dy) = octant.point_to_octant((end.0 - start.0, end.1 - start.1));
+/// assert!(0 <= dy && dy <= dx);
+///
+/// assert_eq!(octant.point_from_octant((dx, dy)), (-3, 4));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Octant(u8);
 
 impl Octant {
-    /// adapted from http://codereview.stackexchange.com/a/95551
+    /// The octant the line going from `start` to `end` is in.
+    ///
+    /// Adapted from <http://codereview.stackexchange.com/a/95551>.
     #[inline]
-    fn from_points(start: Point, end: Point) -> Octant {
+    pub fn from_points(start: Point, end: Point) -> Octant {
         let mut dx = end.0 - start.0;
         let mut dy = end.1 - start.1;
 
@@ -77,8 +102,14 @@ impl Octant {
         Octant(octant)
     }
 
+    /// The index of the octant, from `0` to `7`.
+    pub fn index(&self) -> u8 {
+        self.0
+    }
+
+    /// Folds `p`, from this octant, into the first octant.
     #[inline]
-    fn point_to_octant(&self, p: Point) -> Point {
+    pub fn point_to_octant(&self, p: Point) -> Point {
         match self.0 {
             0 => (p.0, p.1),
             1 => (p.1, p.0),
@@ -92,8 +123,10 @@ impl Octant {
         }
     }
 
+    /// Unfolds `p`, from the first octant, back into this octant. The reverse of
+    /// [`point_to_octant`](Octant::point_to_octant).
     #[inline]
-    fn point_from_octant(&self, p: Point) -> Point {
+    pub fn point_from_octant(&self, p: Point) -> Point {
         match self.0 {
             0 => (p.0, p.1),
             1 => (p.1, p.0),
@@ -159,11 +192,556 @@ impl Iterator for BresenhamLine {
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let len = (self.dx + 1) as usize;
+        let len = (self.x1 - self.x + 1).max(0) as usize;
+        (len, Some(len))
+    }
+
+    /// Skips `n` points at once: the position on the minor axis after `n` steps is computed
+    /// directly, instead of stepping through each point.
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let remaining = (self.x1 - self.x + 1).max(0) as usize;
+        if n >= remaining {
+            self.x = self.x1 + 1;
+            return None;
+        }
+
+        if n > 0 {
+            // `diff` always stays within [dy - dx, dy), which gives the number of steps
+            // along the minor axis.
+            let (dx, dy) = (self.dx as i64, self.dy as i64);
+            let diff = self.diff as i64 + n as i64 * dy;
+            let minor_steps = (diff - dy + dx).div_euclid(dx);
+            self.y += minor_steps as i32;
+            self.diff = (diff - minor_steps * dx) as i32;
+            self.x += n as i32;
+        }
+
+        self.next()
+    }
+}
+
+/// Iterator over the points of a dashed [`BresenhamLine`]: the line alternates between `on`
+/// points yielded and `off` points skipped, starting with the `on` points. With `off` at `0`,
+/// it's a solid line.
+///
+/// # Example
+///
+/// ```rust
+/// use torchbearer::bresenham::DashedLine;
+///
+/// for (x, y) in DashedLine::new((0, 0), (8, 0), 2, 1) {
+///     println!("{}, {}", x, y);
+/// }
+/// ```
+///
+/// Will yield the points:
+///
+/// ```text
+/// x # . # # . # # .
+/// ```
+pub struct DashedLine {
+    line: BresenhamLine,
+    on: u32,
+    off: u32,
+    /// Index of the next point of the line.
+    index: u32,
+}
+
+impl DashedLine {
+    /// Creates a new iterator. Yields the points of the dashes between `start` and `end`,
+    /// inclusive.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `on` is `0`.
+    pub fn new(start: Point, end: Point, on: u32, off: u32) -> DashedLine {
+        if on == 0 {
+            panic!("on should be at least 1, got 0.");
+        }
+
+        DashedLine {
+            line: BresenhamLine::new(start, end),
+            on,
+            off,
+            index: 0,
+        }
+    }
+}
+
+impl Iterator for DashedLine {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for point in self.line.by_ref() {
+            let phase = self.index % (self.on + self.off);
+            self.index += 1;
+            if phase < self.on {
+                return Some(point);
+            }
+        }
+        None
+    }
+}
+
+/// Iterator over every point a line crosses.
+///
+/// Where [`BresenhamLine`] picks one point per step, the supercover line yields all the points
+/// the segment going from the center of `start` to the center of `end` goes through. When the
+/// segment goes exactly through a corner, both points on the sides of the corner are yielded
+/// before the next one, so a line of sight using it never slips between two diagonal walls.
+/// Includes `start` and `end`.
+///
+/// # Example
+///
+/// ```rust
+/// use torchbearer::bresenham::SupercoverLine;
+///
+/// for (x, y) in SupercoverLine::new((0, 0), (3, 1)) {
+///     println!("{}, {}", x, y);
+/// }
+/// ```
+///
+/// Will print:
+///
+/// ```text
+/// (0, 0)
+/// (1, 0)                       x # # .
+/// (2, 0)   corresponding to    . # # x
+/// (1, 1)
+/// (2, 1)
+/// (3, 1)
+/// ```
+pub struct SupercoverLine {
+    x: i32,
+    y: i32,
+    step_x: i32,
+    step_y: i32,
+    /// Length of the line along each axis.
+    dx: i32,
+    dy: i32,
+    /// Steps done along each axis.
+    done_x: i32,
+    done_y: i32,
+    started: bool,
+    /// The points left to yield around a corner.
+    corner: [Option<Point>; 2],
+}
+
+impl SupercoverLine {
+    /// Creates a new iterator. Yields the points crossed between `start` and `end`, inclusive.
+    pub fn new(start: Point, end: Point) -> SupercoverLine {
+        let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+        SupercoverLine {
+            x: start.0,
+            y: start.1,
+            step_x: dx.signum(),
+            step_y: dy.signum(),
+            dx: dx.abs(),
+            dy: dy.abs(),
+            done_x: 0,
+            done_y: 0,
+            started: false,
+            corner: [None, None],
+        }
+    }
+}
+
+impl Iterator for SupercoverLine {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.started = true;
+            return Some((self.x, self.y));
+        }
+        if let Some(point) = self.corner.iter_mut().find_map(Option::take) {
+            return Some(point);
+        }
+        if self.done_x == self.dx && self.done_y == self.dy {
+            return None;
+        }
+
+        // Compares where the line crosses the next vertical and horizontal borders.
+        let decision = (1 + 2 * self.done_x) * self.dy - (1 + 2 * self.done_y) * self.dx;
+        if decision == 0 {
+            let side = (self.x, self.y + self.step_y);
+            self.x += self.step_x;
+            self.y += self.step_y;
+            self.done_x += 1;
+            self.done_y += 1;
+            self.corner = [Some(side), Some((self.x, self.y))];
+            Some((self.x, self.y - self.step_y))
+        } else if decision < 0 {
+            self.x += self.step_x;
+            self.done_x += 1;
+            Some((self.x, self.y))
+        } else {
+            self.y += self.step_y;
+            self.done_y += 1;
+            Some((self.x, self.y))
+        }
+    }
+}
+
+/// Iterator over the points of a line of a given width.
+///
+/// Yields each point whose center is within `width / 2` of the segment going from `start` to
+/// `end`, measured perpendicularly to it, in row-major order. Points past the ends of the
+/// segment are left out. With a width of 1, yields the points of a [`BresenhamLine`] instead,
+/// in the same order.
+///
+/// # Example
+///
+/// ```rust
+/// use torchbearer::bresenham::ThickLine;
+///
+/// for (x, y) in ThickLine::new((0, 0), (4, 0), 3) {
+///     println!("{}, {}", x, y);
+/// }
+/// ```
+///
+/// Will print the points of a rectangle:
+///
+/// ```text
+/// (0, -1)                     # # # # #
+/// (1, -1)   corresponding to  x # # # x
+/// (…)                         # # # # #
+/// (4, 1)
+/// ```
+pub struct ThickLine {
+    inner: ThickLineInner,
+}
+
+enum ThickLineInner {
+    Thin(BresenhamLine),
+    Thick {
+        start: Point,
+        end: Point,
+        width: i32,
+        /// The next point to check.
+        x: i32,
+        y: i32,
+        min_x: i32,
+        max_x: i32,
+        max_y: i32,
+    },
+}
+
+impl ThickLine {
+    /// Creates a new iterator over the points of the line going from `start` to `end`, of
+    /// `width` points.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is lower than 1.
+    pub fn new(start: Point, end: Point, width: i32) -> ThickLine {
+        if width < 1 {
+            panic!("width should be at least 1, got {}.", width);
+        }
+
+        let inner = if width == 1 {
+            ThickLineInner::Thin(BresenhamLine::new(start, end))
+        } else {
+            let half_width = (width + 1) / 2;
+            ThickLineInner::Thick {
+                start,
+                end,
+                width,
+                x: start.0.min(end.0) - half_width,
+                y: start.1.min(end.1) - half_width,
+                min_x: start.0.min(end.0) - half_width,
+                max_x: start.0.max(end.0) + half_width,
+                max_y: start.1.max(end.1) + half_width,
+            }
+        };
+        ThickLine { inner }
+    }
+}
+
+impl Iterator for ThickLine {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (start, end, width, x, y, min_x, max_x, max_y) = match &mut self.inner {
+            ThickLineInner::Thin(line) => return line.next(),
+            ThickLineInner::Thick {
+                start,
+                end,
+                width,
+                x,
+                y,
+                min_x,
+                max_x,
+                max_y,
+            } => (*start, *end, *width as i64, x, y, *min_x, *max_x, *max_y),
+        };
+
+        // Distances are compared squared and scaled by the length of the segment, to stay
+        // with integers.
+        let (dx, dy) = ((end.0 - start.0) as i64, (end.1 - start.1) as i64);
+        let square_length = dx * dx + dy * dy;
+        while *y <= max_y {
+            let point = (*x, *y);
+            if *x < max_x {
+                *x += 1;
+            } else {
+                *x = min_x;
+                *y += 1;
+            }
+
+            let (px, py) = ((point.0 - start.0) as i64, (point.1 - start.1) as i64);
+            let is_on_line = if square_length == 0 {
+                4 * (px * px + py * py) <= width * width
+            } else {
+                let projection = dx * px + dy * py;
+                let cross = dx * py - dy * px;
+                (0..=square_length).contains(&projection)
+                    && 4 * cross * cross <= width * width * square_length
+            };
+            if is_on_line {
+                return Some(point);
+            }
+        }
+        None
+    }
+}
+
+/// Collects the points of a [`BresenhamLine`] going from `start` to `end`, both included.
+///
+/// # Example
+///
+/// ```rust
+/// use torchbearer::bresenham::line;
+///
+/// assert_eq!(line((0, 0), (3, 1)), [(0, 0), (1, 0), (2, 0), (3, 1)]);
+/// ```
+pub fn line(start: Point, end: Point) -> Vec<Point> {
+    BresenhamLine::new(start, end).collect()
+}
+
+/// Creates a [`BresenhamLine`] between two floating point positions, rounded to the nearest
+/// points. Halfway cases are rounded away from zero, like [`f32::round`]: `0.5` becomes `1`, and
+/// `-0.5` becomes `-1`.
+///
+/// # Example
+///
+/// ```rust
+/// use torchbearer::bresenham::{line_f32, BresenhamLine};
+///
+/// let line: Vec<_> = line_f32((0.2, 0.5), (3.7, -1.5)).collect();
+/// let expected: Vec<_> = BresenhamLine::new((0, 1), (4, -2)).collect();
+///
+/// assert_eq!(line, expected);
+/// ```
+pub fn line_f32(start: (f32, f32), end: (f32, f32)) -> BresenhamLine {
+    let round = |(x, y): (f32, f32)| (x.round() as i32, y.round() as i32);
+    BresenhamLine::new(round(start), round(end))
+}
+
+/// Iterator-based Bresenham's line drawing algorithm, in three dimensions.
+///
+/// Same as [`BresenhamLine`], with a `z` axis: the axis along which the line is the longest
+/// drives the other two, each stepping like the minor axis of a 2D line. Includes `start` and
+/// `end`.
+///
+/// # Example
+///
+/// ```rust
+/// use torchbearer::bresenham::BresenhamLine3D;
+///
+/// for (x, y, z) in BresenhamLine3D::new((0, 0, 0), (4, 2, 1)) {
+///     println!("{}, {}, {}", x, y, z);
+/// }
+/// ```
+///
+/// Will print:
+///
+/// ```text
+/// (0, 0, 0)
+/// (1, 0, 0)
+/// (2, 1, 0)
+/// (3, 1, 0)
+/// (4, 2, 1)
+/// ```
+pub struct BresenhamLine3D {
+    current: [i32; 3],
+    steps: [i32; 3],
+    deltas: [i32; 3],
+    /// The index of the axis along which the line is the longest.
+    major: usize,
+    diffs: [i32; 3],
+    remaining: i32,
+}
+
+impl BresenhamLine3D {
+    /// Creates a new iterator. Yields intermediate points between `start`
+    /// and `end`, inclusive.
+    pub fn new(start: (i32, i32, i32), end: (i32, i32, i32)) -> BresenhamLine3D {
+        let start = [start.0, start.1, start.2];
+        let end = [end.0, end.1, end.2];
+        let deltas = [0, 1, 2].map(|axis| (end[axis] - start[axis]).abs());
+        let steps = [0, 1, 2].map(|axis| (end[axis] - start[axis]).signum());
+        let major = (0..3).fold(0, |major, axis| {
+            if deltas[axis] > deltas[major] {
+                axis
+            } else {
+                major
+            }
+        });
+
+        BresenhamLine3D {
+            current: start,
+            steps,
+            deltas,
+            major,
+            diffs: deltas.map(|delta| delta - deltas[major]),
+            remaining: deltas[major] + 1,
+        }
+    }
+}
+
+impl ExactSizeIterator for BresenhamLine3D {}
+
+impl Iterator for BresenhamLine3D {
+    type Item = (i32, i32, i32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+
+        let [x, y, z] = self.current;
+        for axis in 0..3 {
+            if axis == self.major {
+                continue;
+            }
+            if self.diffs[axis] >= 0 {
+                self.current[axis] += self.steps[axis];
+                self.diffs[axis] -= self.deltas[self.major];
+            }
+            self.diffs[axis] += self.deltas[axis];
+        }
+        self.current[self.major] += self.steps[self.major];
+
+        Some((x, y, z))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.remaining as usize;
         (len, Some(len))
     }
 }
 
+/// Iterator-based Xiaolin Wu's anti-aliased line drawing algorithm.
+///
+/// [Xiaolin Wu's line algorithm](https://en.wikipedia.org/wiki/Xiaolin_Wu%27s_line_algorithm)
+/// yields each point with its coverage, from `0.0` to `1.0`, to draw smooth lines. For each
+/// step along the main axis of the line, from `start` to `end`, it yields the pair of points on
+/// each side of the line, the one with the lowest coordinate first. Points with no coverage
+/// are skipped, so `start` and `end` are yielded alone, fully covered.
+///
+/// # Example
+///
+/// ```rust
+/// use torchbearer::bresenham::WuLine;
+///
+/// for ((x, y), coverage) in WuLine::new((0, 0), (4, 1)) {
+///     println!("{}, {}: {}", x, y, coverage);
+/// }
+/// ```
+///
+/// Will print:
+///
+/// ```text
+/// (0, 0): 1.0
+/// (1, 0): 0.75
+/// (1, 1): 0.25
+/// (2, 0): 0.5
+/// (2, 1): 0.5
+/// (3, 0): 0.25
+/// (3, 1): 0.75
+/// (4, 1): 1.0
+/// ```
+pub struct WuLine {
+    start: Point,
+    steep: bool,
+    /// The number of steps along the main axis.
+    length: i32,
+    step: i32,
+    minor_delta: i32,
+    current_step: i32,
+    pending: Option<(Point, f32)>,
+}
+
+impl WuLine {
+    /// Creates a new iterator. Yields the points between `start` and `end`, inclusive, with
+    /// their coverage.
+    pub fn new(start: Point, end: Point) -> WuLine {
+        let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+        let steep = dy.abs() > dx.abs();
+        let (major_delta, minor_delta) = if steep { (dy, dx) } else { (dx, dy) };
+
+        WuLine {
+            start,
+            steep,
+            length: major_delta.abs(),
+            step: major_delta.signum(),
+            minor_delta,
+            current_step: 0,
+            pending: None,
+        }
+    }
+
+    fn point(&self, major: i32, minor: i32) -> Point {
+        if self.steep {
+            (minor, major)
+        } else {
+            (major, minor)
+        }
+    }
+}
+
+impl Iterator for WuLine {
+    type Item = (Point, f32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(pending) = self.pending.take() {
+            return Some(pending);
+        }
+        if self.current_step > self.length {
+            return None;
+        }
+
+        let (major_start, minor_start) = if self.steep {
+            (self.start.1, self.start.0)
+        } else {
+            self.start
+        };
+        let major = major_start + self.current_step * self.step;
+        // The exact position on the minor axis is minor_start + offset / length, kept as a
+        // fraction to avoid rounding errors.
+        let (minor, remainder) = if self.length == 0 {
+            (minor_start, 0)
+        } else {
+            let offset = self.minor_delta * self.current_step;
+            (
+                minor_start + offset.div_euclid(self.length),
+                offset.rem_euclid(self.length),
+            )
+        };
+        self.current_step += 1;
+
+        if remainder == 0 {
+            return Some((self.point(major, minor), 1.));
+        }
+
+        let coverage = remainder as f32 / self.length as f32;
+        self.pending = Some((self.point(major, minor + 1), coverage));
+        Some((self.point(major, minor), 1. - coverage))
+    }
+}
+
 /// Iterator-based Bresenham's circle drawing algorithm.
 ///
 /// [Bresenham's circle drawing algorithm](http://members.chello.at/~easyfilter/bresenham.html)
@@ -221,6 +799,47 @@ impl BresenhamCircle {
             current_quadrant: 0,
         }
     }
+
+    /// Turns the circle into a disc: yields, row by row, every point between the leftmost and
+    /// the rightmost points of the circumference on that row.
+    ///
+    /// Unlike [`FilledCircle`], which keeps the points within an euclidean distance, the disc
+    /// matches the drawn circle exactly, rim included.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use torchbearer::bresenham::BresenhamCircle;
+    ///
+    /// let disc: Vec<_> = BresenhamCircle::new((0, 0), 2).filled().collect();
+    /// ```
+    ///
+    /// Will yield the points:
+    ///
+    /// ```text
+    /// . . . . . . .
+    /// . . # # # . .
+    /// . # # # # # .
+    /// . # # x # # .
+    /// . # # # # # .
+    /// . . # # # . .
+    /// . . . . . . .
+    /// ```
+    pub fn filled(self) -> impl Iterator<Item = Point> {
+        let (center_y, radius) = (self.center.1, self.original_radius.max(0));
+        let mut spans = vec![(i32::MAX, i32::MIN); (2 * radius + 1) as usize];
+        for (x, y) in self {
+            let span = &mut spans[(y - center_y + radius) as usize];
+            *span = (span.0.min(x), span.1.max(x));
+        }
+
+        spans
+            .into_iter()
+            .enumerate()
+            .flat_map(move |(row, (min_x, max_x))| {
+                (min_x..=max_x).map(move |x| (x, center_y - radius + row as i32))
+            })
+    }
 }
 
 impl Iterator for BresenhamCircle {
@@ -263,50 +882,491 @@ impl Iterator for BresenhamCircle {
     }
 }
 
-/// Iterator-based Bresenham's circle drawing algorithm with a twist:
-///
-/// This will output each element of the circle, not ordered (because following octant), and making
-/// sure that each point of the circle move step by step, by x or y but not both at once.
-///
-/// This is needed for a fiew of view implementation that cast rays from the center of the circle all around,
-/// making sure to not miss any point and avoid blind spots.
+/// Iterator over the points of a [`BresenhamCircle`] within an arc, going clockwise from the
+/// `start` angle to the `end` angle, for instance for the firing arc of a turret. An angle of
+/// `0` points east, and a quarter turn points south, as the y axis points down. The arc can go
+/// past east, for instance from `270°` to `90°` for the east half of the circle.
 ///
 /// # Example
 ///
 /// ```
-/// use torchbearer::bresenham::ThickBresenhamCircle;
+/// use torchbearer::bresenham::{Angle, BresenhamArc};
 ///
 /// let center = (0, 0);
 /// let radius = 2;
-/// for (x, y) in ThickBresenhamCircle::new(center, radius) {
+/// for (x, y) in BresenhamArc::new(center, radius, Angle::Degrees(0.), Angle::Degrees(90.)) {
 ///     println!("{}, {}", x, y);
 /// }
 /// ```
 ///
-/// Will print
+/// Will yield the points:
 ///
 /// ```text
-/// (-2, -1)
-/// (-2, 0)
-/// (-2, 1)
-/// (-1, -2)
-/// (-1, -1)                  . . . . . . .
-/// (-1, 1)                   . . # # # . .
-/// (-1, 2)                   . # # . # # .
-/// (0, -2) corresponding to  . # . x . # .
-/// (0, 2)                    . # # . # # .
-/// (1, -2)                   . . # # # . .
-/// (1, -1)                   . . . . . . .
-/// (1, 1)
-/// (1, 2)
-/// (2, -1)
-/// (2, 0)
-/// (2, 1)
+/// . . . . . . .
+/// . . . . . . .
+/// . . . . . . .
+/// . . . x . # .
+/// . . . . . # .
+/// . . . # # . .
+/// . . . . . . .
 /// ```
-pub struct ThickBresenhamCircle {
+pub struct BresenhamArc {
+    circle: BresenhamCircle,
     center: Point,
-    radius: i32,
-    x: i32,
+    start: f32,
+    end: f32,
+    full_turn: bool,
+}
+
+impl BresenhamArc {
+    /// Create new iterator. Yield the points on the circumference of the circle of center
+    /// `center` and radius `radius`, between the angles `start` and `end`. An arc of a full turn
+    /// or more yields the whole circle.
+    pub fn new(center: Point, radius: i32, start: Angle, end: Angle) -> Self {
+        let (start, end) = (start.to_radians(), end.to_radians());
+
+        BresenhamArc {
+            circle: BresenhamCircle::new(center, radius),
+            center,
+            start,
+            end,
+            full_turn: end - start >= TAU,
+        }
+    }
+}
+
+impl Iterator for BresenhamArc {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.full_turn {
+            return self.circle.next();
+        }
+
+        let (center, start, end) = (self.center, self.start, self.end);
+        self.circle.find(|&(x, y)| {
+            let angle = ((y - center.1) as f32).atan2((x - center.0) as f32);
+            is_angle_between(angle, start, end)
+        })
+    }
+}
+
+/// Iterator over the outline of a rectangle, each point once, going clockwise from `min`: east
+/// along the top side, south along the right side, then west and north back to `min`.
+///
+/// # Example
+///
+/// ```
+/// use torchbearer::bresenham::RectOutline;
+///
+/// for (x, y) in RectOutline::new((0, 0), (3, 2)) {
+///     println!("{}, {}", x, y);
+/// }
+/// ```
+///
+/// Will print:
+///
+/// ```text
+/// (0, 0)
+/// (1, 0)
+/// (2, 0)                       x # # #
+/// (3, 0)                       # . . #
+/// (3, 1)   corresponding to    # # # x
+/// (3, 2)
+/// (2, 2)
+/// (1, 2)
+/// (0, 2)
+/// (0, 1)
+/// ```
+pub struct RectOutline {
+    min: Point,
+    max: Point,
+    index: i32,
+    len: i32,
+}
+
+impl RectOutline {
+    /// Create new iterator. Yield all points on the outline of the rectangle going from `min`
+    /// to `max`, inclusive.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is not lower or equal to `max` on both axes.
+    pub fn new(min: Point, max: Point) -> Self {
+        if min.0 > max.0 || min.1 > max.1 {
+            panic!(
+                "min should be lower or equal to max on both axes, got ({}, {}) and ({}, {}).",
+                min.0, min.1, max.0, max.1
+            );
+        }
+
+        let (width, height) = (max.0 - min.0, max.1 - min.1);
+        let len = if width == 0 || height == 0 {
+            // A line, or a single point.
+            width + height + 1
+        } else {
+            2 * (width + height)
+        };
+
+        RectOutline {
+            min,
+            max,
+            index: 0,
+            len,
+        }
+    }
+}
+
+impl Iterator for RectOutline {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.len {
+            return None;
+        }
+
+        let (min, max) = (self.min, self.max);
+        let (width, height) = (max.0 - min.0, max.1 - min.1);
+        let index = self.index;
+        self.index += 1;
+
+        let point = if width == 0 {
+            (min.0, min.1 + index)
+        } else if height == 0 || index < width {
+            (min.0 + index, min.1)
+        } else if index < width + height {
+            (max.0, min.1 + index - width)
+        } else if index < 2 * width + height {
+            (max.0 - (index - width - height), max.1)
+        } else {
+            (min.0, max.1 - (index - 2 * width - height))
+        };
+        Some(point)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.len - self.index) as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for RectOutline {}
+
+/// Iterator over the points around a center, ring by ring, in order of increasing Chebyshev
+/// distance: the center first, then the 8 points at distance 1, the 16 points at distance 2,
+/// and so on. Each ring is a square [`RectOutline`], so unlike [`BresenhamCircle`]s, the rings
+/// cover the whole square without gaps.
+///
+/// # Example
+///
+/// ```
+/// use torchbearer::bresenham::Rings;
+///
+/// let points: Vec<_> = Rings::new((5, 5), 1).collect();
+///
+/// assert_eq!(
+///     points,
+///     [(5, 5), (4, 4), (5, 4), (6, 4), (6, 5), (6, 6), (5, 6), (4, 6), (4, 5)]
+/// );
+/// ```
+pub struct Rings {
+    center: Point,
+    max_radius: i32,
+    radius: i32,
+    outline: RectOutline,
+}
+
+impl Rings {
+    /// Create new iterator. Yield all points at a Chebyshev distance of `max_radius` or less
+    /// from `center`, closest first. Yields nothing if `max_radius` is negative.
+    pub fn new(center: Point, max_radius: i32) -> Self {
+        Rings {
+            center,
+            max_radius,
+            radius: 0,
+            outline: RectOutline::new(center, center),
+        }
+    }
+}
+
+impl Iterator for Rings {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.radius <= self.max_radius {
+            if let Some(point) = self.outline.next() {
+                return Some(point);
+            }
+
+            self.radius += 1;
+            let (x, y) = self.center;
+            let radius = self.radius;
+            self.outline = RectOutline::new((x - radius, y - radius), (x + radius, y + radius));
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.radius > self.max_radius {
+            return (0, Some(0));
+        }
+        // Ring `r` contains `8 * r` points.
+        let (radius, max_radius) = (self.radius as usize, self.max_radius as usize);
+        let len = self.outline.len() + 4 * (max_radius * (max_radius + 1) - radius * (radius + 1));
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Rings {}
+
+/// Fills a polygon, using the even-odd rule: a point is inside if a horizontal line going
+/// from it crosses the edges of the polygon an odd number of times. The edges are part of the
+/// polygon, drawn with [`BresenhamLine`]s, the last vertex being linked back to the first.
+///
+/// Each edge includes its top end but not its bottom end when crossing a row, so that vertices
+/// shared by two edges and horizontal edges are counted correctly.
+///
+/// Returns every point of the polygon once, in row-major order.
+///
+/// # Example
+///
+/// ```
+/// use torchbearer::bresenham::fill_polygon;
+///
+/// let triangle = fill_polygon(&[(0, 0), (4, 0), (0, 4)]);
+/// ```
+///
+/// Will yield the points:
+///
+/// ```text
+/// x # # # x
+/// # # # # .
+/// # # # . .
+/// # # . . .
+/// x . . . .
+/// ```
+pub fn fill_polygon(vertices: &[Point]) -> Vec<Point> {
+    let mut points = vec![];
+    let edges = vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(&start, &end)| (start, end));
+
+    for (start, end) in edges.clone() {
+        points.extend(BresenhamLine::new(start, end));
+    }
+
+    let min_y = vertices.iter().map(|&(_, y)| y).min().unwrap_or(0);
+    let max_y = vertices.iter().map(|&(_, y)| y).max().unwrap_or(-1);
+    let mut crossings = vec![];
+    for y in min_y..=max_y {
+        crossings.clear();
+        for ((start_x, start_y), (end_x, end_y)) in edges.clone() {
+            let (top, bottom) = (start_y.min(end_y), start_y.max(end_y));
+            if top <= y && y < bottom {
+                let t = (y - start_y) as f64 / (end_y - start_y) as f64;
+                crossings.push(start_x as f64 + t * (end_x - start_x) as f64);
+            }
+        }
+        crossings.sort_by(f64::total_cmp);
+
+        for span in crossings.chunks_exact(2) {
+            let (left, right) = (span[0].ceil() as i32, span[1].floor() as i32);
+            points.extend((left..=right).map(|x| (x, y)));
+        }
+    }
+
+    points.sort_unstable_by_key(|&(x, y)| (y, x));
+    points.dedup();
+    points
+}
+
+/// Iterator-based Bresenham's ellipse drawing algorithm.
+///
+/// [Bresenham's ellipse drawing algorithm](http://members.chello.at/~easyfilter/bresenham.html)
+/// draws the circumference of an ellipse whose axes are aligned with the grid, `rx` being the
+/// horizontal radius and `ry` the vertical one. Each point is yielded once, quadrant by quadrant,
+/// each quadrant going from the horizontal axis to the vertical axis.
+///
+/// # Example
+///
+/// ```
+/// use torchbearer::bresenham::BresenhamEllipse;
+///
+/// let center = (0, 0);
+/// for (x, y) in BresenhamEllipse::new(center, 3, 2) {
+///     println!("{}, {}", x, y);
+/// }
+/// ```
+///
+/// Will yield the points:
+///
+/// ```text
+/// . . . . . . . . .
+/// . . . # # # . . .
+/// . # # . . . # # .
+/// . # . . x . . # .
+/// . # # . . . # # .
+/// . . . # # # . . .
+/// . . . . . . . . .
+/// ```
+pub struct BresenhamEllipse {
+    center: Point,
+    rx: i32,
+    ry: i32,
+    x: i32,
+    y: i32,
+    err: i64,
+    /// Once the main loop is done, flat ellipses still need their tips.
+    tip: bool,
+    current_quadrant: i32,
+    /// With a radius of `0`, the ellipse is a segment through the center, drawn as a line.
+    segment: Option<BresenhamLine>,
+}
+
+impl BresenhamEllipse {
+    /// Create new iterator. Yield all points on the circumference of the ellipse
+    /// of center `center`, horizontal radius `rx` and vertical radius `ry`. If one of the radii
+    /// is `0`, the ellipse is flattened into the segment going through the center.
+    pub fn new(center: Point, rx: i32, ry: i32) -> Self {
+        let segment = if rx == 0 || ry == 0 {
+            let (x, y) = center;
+            Some(BresenhamLine::new((x - rx, y - ry), (x + rx, y + ry)))
+        } else {
+            None
+        };
+        let mut ellipse = BresenhamEllipse {
+            center,
+            rx,
+            ry,
+            x: 0,
+            y: 0,
+            err: 0,
+            tip: false,
+            current_quadrant: 0,
+            segment,
+        };
+        ellipse.reset();
+        ellipse
+    }
+
+    fn reset(&mut self) {
+        let square_ry = self.ry as i64 * self.ry as i64;
+        self.x = -self.rx;
+        self.y = 0;
+        self.err = self.x as i64 * (2 * square_ry + self.x as i64) + square_ry;
+        self.tip = false;
+    }
+
+    /// The next point of the first quadrant, as `(-x, y)`, or `None` once it's complete.
+    fn next_in_quadrant(&mut self) -> Option<Point> {
+        if self.tip || self.x > 0 {
+            self.tip = true;
+            if self.y >= self.ry {
+                return None;
+            }
+            self.y += 1;
+            return Some((0, self.y));
+        }
+
+        let point = (-self.x, self.y);
+        let (square_rx, square_ry) = (
+            self.rx as i64 * self.rx as i64,
+            self.ry as i64 * self.ry as i64,
+        );
+        let err = 2 * self.err;
+        if err >= (self.x as i64 * 2 + 1) * square_ry {
+            self.x += 1;
+            self.err += (self.x as i64 * 2 + 1) * square_ry;
+        }
+        if err <= (self.y as i64 * 2 + 1) * square_rx {
+            self.y += 1;
+            self.err += (self.y as i64 * 2 + 1) * square_rx;
+        }
+        Some(point)
+    }
+}
+
+impl Iterator for BresenhamEllipse {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(segment) = &mut self.segment {
+            return segment.next();
+        }
+
+        while self.current_quadrant < 4 {
+            let (u, v) = match self.next_in_quadrant() {
+                Some(point) => point,
+                None => {
+                    // Reset for next quadrant
+                    self.current_quadrant += 1;
+                    self.reset();
+                    continue;
+                }
+            };
+
+            // Each quadrant leaves out one of its ends, so that the points on the axes are
+            // only yielded once.
+            let (dx, dy, owned) = match self.current_quadrant {
+                0 => (u, v, u > 0),
+                1 => (-u, v, v > 0),
+                2 => (-u, -v, u > 0),
+                _ => (u, -v, v > 0),
+            };
+            if owned {
+                return Some((self.center.0 + dx, self.center.1 + dy));
+            }
+        }
+
+        None
+    }
+}
+
+/// Iterator-based Bresenham's circle drawing algorithm with a twist:
+///
+/// This will output each element of the circle, not ordered (because following octant), and making
+/// sure that each point of the circle move step by step, by x or y but not both at once.
+///
+/// This is needed for a fiew of view implementation that cast rays from the center of the circle all around,
+/// making sure to not miss any point and avoid blind spots.
+///
+/// # Example
+///
+/// ```
+/// use torchbearer::bresenham::ThickBresenhamCircle;
+///
+/// let center = (0, 0);
+/// let radius = 2;
+/// for (x, y) in ThickBresenhamCircle::new(center, radius) {
+///     println!("{}, {}", x, y);
+/// }
+/// ```
+///
+/// Will print
+///
+/// ```text
+/// (-2, -1)
+/// (-2, 0)
+/// (-2, 1)
+/// (-1, -2)
+/// (-1, -1)                  . . . . . . .
+/// (-1, 1)                   . . # # # . .
+/// (-1, 2)                   . # # . # # .
+/// (0, -2) corresponding to  . # . x . # .
+/// (0, 2)                    . # # . # # .
+/// (1, -2)                   . . # # # . .
+/// (1, -1)                   . . . . . . .
+/// (1, 1)
+/// (1, 2)
+/// (2, -1)
+/// (2, 0)
+/// (2, 1)
+/// ```
+pub struct ThickBresenhamCircle {
+    center: Point,
+    radius: i32,
+    x: i32,
     y: i32,
     err: i32,
     moved: bool,
@@ -361,47 +1421,251 @@ impl Iterator for ThickBresenhamCircle {
                 self.y += 1;
             }
 
-            self.current_step += 1;
+            self.current_step += 1;
+        }
+
+        let point = match self.octant {
+            0 => (self.center.0 + self.x, self.center.1 + self.y),
+            1 => (self.center.0 + self.y, self.center.1 + self.x),
+            2 => (self.center.0 - self.y, self.center.1 + self.x),
+            3 => (self.center.0 - self.x, self.center.1 + self.y),
+            4 => (self.center.0 - self.x, self.center.1 - self.y),
+            5 => (self.center.0 - self.y, self.center.1 - self.x),
+            6 => (self.center.0 + self.y, self.center.1 - self.x),
+            7 => (self.center.0 + self.x, self.center.1 - self.y),
+            _ => unreachable!(),
+        };
+
+        let step = if self.current_step == 0 || self.x <= self.y {
+            2
+        } else {
+            1
+        };
+        if self.octant + step < 8 {
+            self.octant += step;
+        } else {
+            self.octant = -1;
+        }
+
+        Some(point)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let size = self.radius as usize * 8;
+        (size, Some(size))
+    }
+}
+
+impl ExactSizeIterator for ThickBresenhamCircle {}
+
+/// Iterator yielding every point of a disc: all the points whose euclidean distance to the
+/// center is lower or equal to the radius. Points are yielded row by row, each row being a
+/// span computed from the circle equation, rather than checking every point of the bounding
+/// square.
+///
+/// # Example
+///
+/// ```
+/// use torchbearer::bresenham::FilledCircle;
+///
+/// let center = (0, 0);
+/// let radius = 2;
+/// for (x, y) in FilledCircle::new(center, radius) {
+///     println!("{}, {}", x, y);
+/// }
+/// ```
+///
+/// Will yield the points:
+///
+/// ```text
+/// . . . . . . .
+/// . . . # . . .
+/// . . # # # . .
+/// . # # x # # .
+/// . . # # # . .
+/// . . . # . . .
+/// . . . . . . .
+/// ```
+pub struct FilledCircle {
+    center: Point,
+    radius: i32,
+    x: i32,
+    y: i32,
+    /// The last x of the current row.
+    span_end: i32,
+}
+
+impl FilledCircle {
+    /// Create new iterator. Yield all points within the disc of center `center` and
+    /// radius `radius`. A negative radius yields nothing.
+    pub fn new(center: Point, radius: i32) -> Self {
+        FilledCircle {
+            center,
+            radius,
+            x: 0,
+            y: -radius,
+            span_end: -1,
+        }
+    }
+
+    /// The half width of the row `y` of a disc of radius `radius`.
+    fn half_width(radius: i32, y: i32) -> i32 {
+        (radius * radius - y * y).isqrt()
+    }
+}
+
+impl Iterator for FilledCircle {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.x > self.span_end {
+            // Moving on to the next row, rows being spans from -half_width to half_width.
+            if self.span_end >= 0 {
+                self.y += 1;
+            }
+            if self.y > self.radius {
+                return None;
+            }
+            self.span_end = FilledCircle::half_width(self.radius, self.y);
+            self.x = -self.span_end;
+        }
+
+        let point = (self.center.0 + self.x, self.center.1 + self.y);
+        self.x += 1;
+        Some(point)
+    }
+}
+
+/// Yields the ring of points added to a disc when its radius grows from `radius - 1` to `radius`:
+/// all the points whose euclidean distance to the center is in `(radius - 1, radius]`.
+///
+/// Useful to animate an effect growing over several frames, like an explosion. A radius of `0`
+/// yields the center only.
+///
+/// # Example
+///
+/// ```
+/// use torchbearer::bresenham::{disc_ring, FilledCircle};
+///
+/// let center = (0, 0);
+/// let mut disc: Vec<_> = (0..=3).flat_map(|radius| disc_ring(center, radius)).collect();
+/// disc.sort();
+///
+/// let mut expected: Vec<_> = FilledCircle::new(center, 3).collect();
+/// expected.sort();
+///
+/// assert_eq!(disc, expected);
+/// ```
+pub fn disc_ring(center: Point, radius: i32) -> impl Iterator<Item = Point> {
+    (-radius..=radius).flat_map(move |y| {
+        // Each row of the ring is the row of the disc, minus the row of the inner disc if any.
+        let outer = FilledCircle::half_width(radius, y);
+        let inner = if y.abs() < radius {
+            FilledCircle::half_width(radius - 1, y)
+        } else {
+            -1
+        };
+        // Without an inner row, the left span reaches 0 and the right one starts at 1.
+        let left = -outer..=-inner - 1;
+        let right = (inner + 1).max(1)..=outer;
+        left.chain(right).map(move |x| (center.0 + x, center.1 + y))
+    })
+}
+
+/// An angle, in degrees or in radians.
+///
+/// On a grid, `0` points east and angles grow clockwise, as the y axis points down: a quarter
+/// turn points south.
+///
+/// Angles can be added and subtracted. Adding two angles in degrees gives an angle in degrees,
+/// any other combination gives an angle in radians. Equality compares the variant and the value:
+/// `Angle::Degrees(180.)` is not equal to `Angle::Radians(PI)`, compare
+/// [`to_radians`](Angle::to_radians) instead.
+///
+/// # Example
+///
+/// ```
+/// use torchbearer::bresenham::Angle;
+///
+/// let facing = Angle::Degrees(350.) + Angle::Degrees(20.);
+///
+/// assert_eq!(facing, Angle::Degrees(370.));
+/// assert_eq!(facing.normalized(), Angle::Degrees(10.));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Angle {
+    /// An angle in degrees, a full turn being `360.0`.
+    Degrees(f32),
+    /// An angle in radians, a full turn being `2π`.
+    Radians(f32),
+}
+
+impl Angle {
+    /// The value of the angle in radians.
+    pub fn to_radians(&self) -> f32 {
+        match *self {
+            Angle::Degrees(degrees) => degrees.to_radians(),
+            Angle::Radians(radians) => radians,
+        }
+    }
+
+    /// The value of the angle in degrees.
+    pub fn to_degrees(&self) -> f32 {
+        match *self {
+            Angle::Degrees(degrees) => degrees,
+            Angle::Radians(radians) => radians.to_degrees(),
+        }
+    }
+
+    /// The same angle, within a single turn: between `0` included and `2π` excluded, or `360°`
+    /// for an angle in degrees. Negative angles become positive.
+    pub fn normalized(&self) -> Angle {
+        match *self {
+            Angle::Degrees(degrees) => Angle::Degrees(degrees.rem_euclid(360.)),
+            Angle::Radians(radians) => Angle::Radians(radians.rem_euclid(TAU)),
         }
+    }
+}
 
-        let point = match self.octant {
-            0 => (self.center.0 + self.x, self.center.1 + self.y),
-            1 => (self.center.0 + self.y, self.center.1 + self.x),
-            2 => (self.center.0 - self.y, self.center.1 + self.x),
-            3 => (self.center.0 - self.x, self.center.1 + self.y),
-            4 => (self.center.0 - self.x, self.center.1 - self.y),
-            5 => (self.center.0 - self.y, self.center.1 - self.x),
-            6 => (self.center.0 + self.y, self.center.1 - self.x),
-            7 => (self.center.0 + self.x, self.center.1 - self.y),
-            _ => unreachable!(),
-        };
+impl Add for Angle {
+    type Output = Angle;
 
-        let step = if self.current_step == 0 || self.x <= self.y {
-            2
-        } else {
-            1
-        };
-        if self.octant + step < 8 {
-            self.octant += step;
-        } else {
-            self.octant = -1;
+    fn add(self, other: Angle) -> Angle {
+        match (self, other) {
+            (Angle::Degrees(a), Angle::Degrees(b)) => Angle::Degrees(a + b),
+            _ => Angle::Radians(self.to_radians() + other.to_radians()),
         }
-
-        Some(point)
     }
+}
 
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        let size = self.radius as usize * 8;
-        (size, Some(size))
+impl Sub for Angle {
+    type Output = Angle;
+
+    fn sub(self, other: Angle) -> Angle {
+        match (self, other) {
+            (Angle::Degrees(a), Angle::Degrees(b)) => Angle::Degrees(a - b),
+            _ => Angle::Radians(self.to_radians() - other.to_radians()),
+        }
     }
 }
 
-impl ExactSizeIterator for ThickBresenhamCircle {}
+/// Is `angle` within the arc going clockwise from `start` to `end`, all in radians.
+pub(crate) fn is_angle_between(angle: f32, start: f32, end: f32) -> bool {
+    let span = (end - start).rem_euclid(TAU);
+    (angle - start).rem_euclid(TAU) <= span
+}
 
 #[cfg(test)]
 mod tests {
-    use super::{BresenhamCircle, BresenhamLine, ThickBresenhamCircle};
-    use std::vec::Vec;
+    use super::{
+        disc_ring, fill_polygon, line, line_f32, Angle, BresenhamArc, BresenhamCircle,
+        BresenhamEllipse, BresenhamLine, BresenhamLine3D, DashedLine, FilledCircle, Octant,
+        RectOutline, Rings, SupercoverLine, ThickBresenhamCircle, ThickLine, WuLine,
+    };
+    use std::{
+        f32::consts::{FRAC_PI_2, PI, TAU},
+        vec::Vec,
+    };
 
     #[test]
     fn test_wp_example() {
@@ -504,4 +1768,579 @@ mod tests {
 
         assert_eq!(expected, result);
     }
+
+    #[test]
+    fn filled_circle_radius_1() {
+        let mut result = FilledCircle::new((1, 1), 1).collect::<Vec<_>>();
+        result.sort();
+
+        assert_eq!(result, [(0, 1), (1, 0), (1, 1), (1, 2), (2, 1)]);
+    }
+
+    #[test]
+    fn filled_circle_matches_euclidean_distance() {
+        for radius in [-1, 0, 2, 7, 60] {
+            let result = FilledCircle::new((5, -3), radius).collect::<Vec<_>>();
+
+            let mut expected = vec![];
+            for y in -radius..=radius {
+                for x in -radius..=radius {
+                    if x * x + y * y <= radius * radius {
+                        expected.push((5 + x, -3 + y));
+                    }
+                }
+            }
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    fn disc_rings_make_a_disc() {
+        let center = (3, -2);
+
+        for radius in 0..=12 {
+            let mut rings = (0..=radius)
+                .flat_map(|radius| disc_ring(center, radius))
+                .collect::<Vec<_>>();
+            let count = rings.len();
+            rings.sort();
+            rings.dedup();
+
+            let mut disc = FilledCircle::new(center, radius).collect::<Vec<_>>();
+            disc.sort();
+
+            assert_eq!(count, rings.len(), "Rings should not overlap");
+            assert_eq!(rings, disc);
+        }
+    }
+
+    #[test]
+    fn disc_ring_zero_is_center() {
+        assert_eq!(disc_ring((2, 2), 0).collect::<Vec<_>>(), [(2, 2)]);
+    }
+
+    #[test]
+    fn wu_line_coverage() {
+        let line: Vec<_> = WuLine::new((0, 0), (4, 1)).collect();
+
+        assert_eq!(
+            line,
+            [
+                ((0, 0), 1.),
+                ((1, 0), 0.75),
+                ((1, 1), 0.25),
+                ((2, 0), 0.5),
+                ((2, 1), 0.5),
+                ((3, 0), 0.25),
+                ((3, 1), 0.75),
+                ((4, 1), 1.)
+            ]
+        );
+    }
+
+    #[test]
+    fn wu_line_steep_and_reversed() {
+        let line: Vec<_> = WuLine::new((0, 0), (-1, -4)).collect();
+
+        assert_eq!(
+            line,
+            [
+                ((0, 0), 1.),
+                ((-1, -1), 0.25),
+                ((0, -1), 0.75),
+                ((-1, -2), 0.5),
+                ((0, -2), 0.5),
+                ((-1, -3), 0.75),
+                ((0, -3), 0.25),
+                ((-1, -4), 1.)
+            ]
+        );
+    }
+
+    #[test]
+    fn wu_line_straight_lines_are_fully_covered() {
+        let horizontal: Vec<_> = WuLine::new((0, 0), (3, 0)).collect();
+        let diagonal: Vec<_> = WuLine::new((0, 0), (-3, 3)).collect();
+        let single: Vec<_> = WuLine::new((2, 2), (2, 2)).collect();
+
+        assert_eq!(
+            horizontal,
+            [((0, 0), 1.), ((1, 0), 1.), ((2, 0), 1.), ((3, 0), 1.)]
+        );
+        assert_eq!(
+            diagonal,
+            [((0, 0), 1.), ((-1, 1), 1.), ((-2, 2), 1.), ((-3, 3), 1.)]
+        );
+        assert_eq!(single, [((2, 2), 1.)]);
+    }
+
+    #[test]
+    fn line_3d_along_z() {
+        let line = BresenhamLine3D::new((1, 2, 3), (1, 2, -1));
+        assert_eq!(line.len(), 5);
+
+        let res: Vec<_> = line.collect();
+        assert_eq!(
+            res,
+            [(1, 2, 3), (1, 2, 2), (1, 2, 1), (1, 2, 0), (1, 2, -1)]
+        );
+    }
+
+    #[test]
+    fn line_3d_diagonal() {
+        let res: Vec<_> = BresenhamLine3D::new((0, 0, 0), (3, -3, 3)).collect();
+
+        assert_eq!(res, [(0, 0, 0), (1, -1, 1), (2, -2, 2), (3, -3, 3)]);
+    }
+
+    #[test]
+    fn line_3d_matches_2d_line_on_a_plane() {
+        for end in [(6, 4), (-6, 4), (4, -6), (-3, -7), (5, 0), (0, -2), (0, 0)] {
+            let line_2d: Vec<_> = BresenhamLine::new((0, 1), end)
+                .map(|(x, y)| (x, y, 5))
+                .collect();
+            let line_3d: Vec<_> = BresenhamLine3D::new((0, 1, 5), (end.0, end.1, 5)).collect();
+
+            assert_eq!(line_3d, line_2d);
+        }
+    }
+
+    #[test]
+    fn thick_line_of_width_1_is_a_bresenham_line() {
+        for end in [(6, 4), (-6, 4), (4, -6), (0, 0)] {
+            let thick: Vec<_> = ThickLine::new((0, 1), end, 1).collect();
+            let line: Vec<_> = BresenhamLine::new((0, 1), end).collect();
+
+            assert_eq!(thick, line);
+        }
+    }
+
+    #[test]
+    fn thick_line_horizontal() {
+        let res: Vec<_> = ThickLine::new((0, 0), (4, 0), 3).collect();
+
+        let mut expected = vec![];
+        for y in -1..=1 {
+            for x in 0..=4 {
+                expected.push((x, y));
+            }
+        }
+        assert_eq!(res, expected);
+    }
+
+    #[test]
+    fn thick_line_diagonal_has_no_duplicates() {
+        let res: Vec<_> = ThickLine::new((0, 0), (5, 5), 4).collect();
+        let mut deduplicated = res.clone();
+        deduplicated.sort_unstable();
+        deduplicated.dedup();
+
+        assert_eq!(res.len(), deduplicated.len());
+        for point in BresenhamLine::new((0, 0), (5, 5)) {
+            assert!(res.contains(&point));
+        }
+        assert!(res.contains(&(1, 0)));
+        assert!(res.contains(&(2, 0)));
+        assert!(!res.contains(&(3, 0)));
+        assert!(!res.contains(&(-1, -1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "width should be at least 1, got 0.")]
+    fn thick_line_zero_width_panics() {
+        ThickLine::new((0, 0), (5, 5), 0);
+    }
+
+    #[test]
+    fn supercover_line_visits_corner_points() {
+        let line: Vec<_> = BresenhamLine::new((0, 0), (2, 2)).collect();
+        let supercover: Vec<_> = SupercoverLine::new((0, 0), (2, 2)).collect();
+
+        assert_eq!(line, [(0, 0), (1, 1), (2, 2)]);
+        assert_eq!(
+            supercover,
+            [(0, 0), (1, 0), (0, 1), (1, 1), (2, 1), (1, 2), (2, 2)]
+        );
+    }
+
+    #[test]
+    fn supercover_line_steps_one_axis_at_a_time() {
+        let supercover: Vec<_> = SupercoverLine::new((0, 0), (3, 1)).collect();
+        assert_eq!(supercover, [(0, 0), (1, 0), (2, 0), (1, 1), (2, 1), (3, 1)]);
+
+        // None of these lines go exactly through a corner.
+        for end in [(6, 4), (-6, 4), (4, -6), (-3, -7), (5, 1), (0, 1)] {
+            let supercover: Vec<_> = SupercoverLine::new((0, 1), end).collect();
+            let (dx, dy) = (end.0, end.1 - 1);
+
+            assert_eq!(supercover.len() as i32, 1 + dx.abs() + dy.abs());
+            assert_eq!(supercover.last(), Some(&end));
+            for pair in supercover.windows(2) {
+                let (a, b) = (pair[0], pair[1]);
+                assert_eq!((a.0 - b.0).abs() + (a.1 - b.1).abs(), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn ellipse_3_2() {
+        let ellipse: Vec<_> = BresenhamEllipse::new((0, 0), 3, 2).collect();
+
+        assert_eq!(
+            ellipse,
+            [
+                (3, 0),
+                (3, 1),
+                (2, 1),
+                (1, 2),
+                (-3, 1),
+                (-2, 1),
+                (-1, 2),
+                (0, 2),
+                (-3, 0),
+                (-3, -1),
+                (-2, -1),
+                (-1, -2),
+                (3, -1),
+                (2, -1),
+                (1, -2),
+                (0, -2)
+            ]
+        );
+    }
+
+    #[test]
+    fn ellipse_flat_has_tips() {
+        let mut ellipse: Vec<_> = BresenhamEllipse::new((0, 0), 1, 6).collect();
+        ellipse.sort_unstable();
+
+        let mut expected = vec![(0, -6), (0, -5), (0, 5), (0, 6)];
+        for y in -4..=4 {
+            expected.push((-1, y));
+            expected.push((1, y));
+        }
+        expected.sort_unstable();
+        assert_eq!(ellipse, expected);
+    }
+
+    #[test]
+    fn ellipse_with_a_zero_radius_is_a_segment() {
+        let horizontal: Vec<_> = BresenhamEllipse::new((5, 5), 3, 0).collect();
+        assert_eq!(horizontal, (2..=8).map(|x| (x, 5)).collect::<Vec<_>>());
+
+        let vertical: Vec<_> = BresenhamEllipse::new((5, 5), 0, 3).collect();
+        assert_eq!(vertical, (2..=8).map(|y| (5, y)).collect::<Vec<_>>());
+
+        let point: Vec<_> = BresenhamEllipse::new((5, 5), 0, 0).collect();
+        assert_eq!(point, [(5, 5)]);
+    }
+
+    #[test]
+    fn ellipse_with_equal_radii_is_a_circle() {
+        let mut ellipse: Vec<_> = BresenhamEllipse::new((0, 0), 5, 5).collect();
+        let mut circle: Vec<_> = BresenhamCircle::new((0, 0), 5).collect();
+        ellipse.sort_unstable();
+        circle.sort_unstable();
+
+        assert_eq!(ellipse, circle);
+    }
+
+    #[test]
+    fn arc_quarter_of_a_circle() {
+        let circle: Vec<_> = BresenhamCircle::new((0, 0), 10).collect();
+        let arc: Vec<_> =
+            BresenhamArc::new((0, 0), 10, Angle::Degrees(0.), Angle::Degrees(90.)).collect();
+
+        assert!((arc.len() as i32 - circle.len() as i32 / 4).abs() <= 2);
+        assert!(arc.contains(&(10, 0)));
+        assert!(arc.contains(&(0, 10)));
+        assert!(arc.iter().all(|&(x, y)| x >= 0 && y >= 0));
+    }
+
+    #[test]
+    fn arc_wraps_around_east() {
+        let arc: Vec<_> =
+            BresenhamArc::new((0, 0), 10, Angle::Degrees(270.), Angle::Degrees(90.)).collect();
+        let east_half: Vec<_> = BresenhamCircle::new((0, 0), 10)
+            .filter(|&(x, _)| x >= 0)
+            .collect();
+
+        assert_eq!(arc, east_half);
+    }
+
+    #[test]
+    fn arc_full_turn_is_the_circle() {
+        let arc: Vec<_> =
+            BresenhamArc::new((3, 3), 5, Angle::Degrees(45.), Angle::Degrees(405.)).collect();
+        let circle: Vec<_> = BresenhamCircle::new((3, 3), 5).collect();
+
+        assert_eq!(arc, circle);
+    }
+
+    #[test]
+    fn dashed_line() {
+        let res: Vec<_> = DashedLine::new((0, 0), (8, 0), 2, 1).collect();
+
+        assert_eq!(res, [(0, 0), (1, 0), (3, 0), (4, 0), (6, 0), (7, 0)]);
+    }
+
+    #[test]
+    fn dashed_line_without_gaps_is_solid() {
+        let dashed: Vec<_> = DashedLine::new((0, 1), (6, 4), 1, 0).collect();
+        let line: Vec<_> = BresenhamLine::new((0, 1), (6, 4)).collect();
+
+        assert_eq!(dashed, line);
+    }
+
+    #[test]
+    #[should_panic(expected = "on should be at least 1, got 0.")]
+    fn dashed_line_without_dashes_panics() {
+        DashedLine::new((0, 0), (8, 0), 0, 1);
+    }
+
+    #[test]
+    fn rect_outline_clockwise() {
+        let outline = RectOutline::new((0, 0), (3, 2));
+        assert_eq!(outline.len(), 10);
+
+        let res: Vec<_> = outline.collect();
+        assert_eq!(
+            res,
+            [
+                (0, 0),
+                (1, 0),
+                (2, 0),
+                (3, 0),
+                (3, 1),
+                (3, 2),
+                (2, 2),
+                (1, 2),
+                (0, 2),
+                (0, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn rect_outline_flat_rectangles() {
+        let row: Vec<_> = RectOutline::new((1, 5), (4, 5)).collect();
+        let column: Vec<_> = RectOutline::new((1, 5), (1, 7)).collect();
+        let point: Vec<_> = RectOutline::new((1, 5), (1, 5)).collect();
+
+        assert_eq!(row, [(1, 5), (2, 5), (3, 5), (4, 5)]);
+        assert_eq!(column, [(1, 5), (1, 6), (1, 7)]);
+        assert_eq!(point, [(1, 5)]);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "min should be lower or equal to max on both axes, got (3, 0) and (0, 2)."
+    )]
+    fn rect_outline_inverted_panics() {
+        RectOutline::new((3, 0), (0, 2));
+    }
+
+    #[test]
+    fn rings_grow_by_eight_points() {
+        let center = (-2, 7);
+        let mut rings = Rings::new(center, 5);
+        assert_eq!(rings.len(), 121);
+        assert_eq!(rings.next(), Some(center));
+
+        for radius in 1..=5 {
+            let ring: Vec<_> = rings.by_ref().take(8 * radius as usize).collect();
+
+            assert_eq!(ring.len(), 8 * radius as usize);
+            for (x, y) in ring {
+                assert_eq!((x - center.0).abs().max((y - center.1).abs()), radius);
+            }
+        }
+        assert_eq!(rings.len(), 0);
+        assert_eq!(rings.next(), None);
+        assert_eq!(Rings::new(center, -1).count(), 0);
+    }
+
+    #[test]
+    fn line_f32_rounds_half_away_from_zero() {
+        let line: Vec<_> = line_f32((0.5, -0.5), (2.5, 1.49)).collect();
+        assert_eq!(line, [(1, -1), (2, 0), (3, 1)]);
+
+        let line: Vec<_> = line_f32((-2.5, 0.49), (-0.51, -1.5)).collect();
+        assert_eq!(
+            line,
+            BresenhamLine::new((-3, 0), (-1, -2)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn line_nth_matches_stepping() {
+        for end in [
+            (6, 4),
+            (-6, 4),
+            (4, -6),
+            (-3, -7),
+            (50, 13),
+            (0, 9),
+            (7, 7),
+            (0, 0),
+        ] {
+            let points: Vec<_> = BresenhamLine::new((0, 1), end).collect();
+
+            for n in 0..points.len() + 2 {
+                let mut line = BresenhamLine::new((0, 1), end);
+                assert_eq!(line.nth(n), points.get(n).copied());
+                assert_eq!(line.len(), points.len().saturating_sub(n + 1));
+                assert_eq!(
+                    line.collect::<Vec<_>>(),
+                    points[(n + 1).min(points.len())..]
+                );
+            }
+
+            let mut line = BresenhamLine::new((0, 1), end);
+            let sparse: Vec<_> = std::iter::from_fn(|| line.nth(2)).collect();
+            let expected: Vec<_> = points.iter().skip(2).step_by(3).copied().collect();
+            assert_eq!(sparse, expected);
+        }
+    }
+
+    #[test]
+    fn octant_folds_into_the_first_octant() {
+        let mut indices = vec![];
+        for delta in [
+            (5, 2),
+            (2, 5),
+            (-2, 5),
+            (-5, 2),
+            (-5, -2),
+            (-2, -5),
+            (2, -5),
+            (5, -2),
+        ] {
+            let octant = Octant::from_points((1, 1), (1 + delta.0, 1 + delta.1));
+            let folded = octant.point_to_octant(delta);
+
+            assert_eq!(folded, (5, 2));
+            assert_eq!(octant.point_from_octant(folded), delta);
+            indices.push(octant.index());
+        }
+
+        indices.sort_unstable();
+        assert_eq!(indices, [0, 1, 2, 3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn line_matches_iterator() {
+        assert_eq!(
+            line((0, 1), (6, 4)),
+            BresenhamLine::new((0, 1), (6, 4)).collect::<Vec<_>>()
+        );
+        assert_eq!(line((2, 2), (2, 2)), [(2, 2)]);
+    }
+
+    #[test]
+    fn fill_polygon_rectangle_and_triangle() {
+        let rectangle = fill_polygon(&[(0, 0), (4, 0), (4, 3), (0, 3)]);
+        let mut expected = vec![];
+        for y in 0..=3 {
+            for x in 0..=4 {
+                expected.push((x, y));
+            }
+        }
+        assert_eq!(rectangle, expected);
+
+        let triangle = fill_polygon(&[(0, 0), (4, 0), (0, 4)]);
+        assert_eq!(triangle.len(), 15);
+        assert!(triangle
+            .iter()
+            .all(|&(x, y)| x >= 0 && y >= 0 && x + y <= 4));
+    }
+
+    #[test]
+    fn fill_polygon_concave() {
+        // A U shape, with a notch going down from the top.
+        let u_shape = fill_polygon(&[
+            (0, 0),
+            (2, 0),
+            (2, 3),
+            (4, 3),
+            (4, 0),
+            (6, 0),
+            (6, 5),
+            (0, 5),
+        ]);
+
+        assert_eq!(u_shape.len(), 7 * 6 - 3);
+        for y in 0..3 {
+            assert!(!u_shape.contains(&(3, y)));
+        }
+        assert!(u_shape.contains(&(3, 3)));
+        assert!(u_shape.contains(&(5, 1)));
+    }
+
+    #[test]
+    fn fill_polygon_degenerate() {
+        assert!(fill_polygon(&[]).is_empty());
+        assert_eq!(fill_polygon(&[(2, 3)]), [(2, 3)]);
+        assert_eq!(fill_polygon(&[(0, 0), (2, 0)]), [(0, 0), (1, 0), (2, 0)]);
+    }
+
+    #[test]
+    fn circle_filled_follows_the_rim() {
+        let disc: Vec<_> = BresenhamCircle::new((1, 1), 2).filled().collect();
+
+        let mut expected = vec![];
+        for (y, half_width) in [(-1, 1), (0, 2), (1, 2), (2, 2), (3, 1)] {
+            for x in 1 - half_width..=1 + half_width {
+                expected.push((x, y));
+            }
+        }
+        assert_eq!(disc, expected);
+
+        let disc: Vec<_> = BresenhamCircle::new((0, 0), 10).filled().collect();
+        for point in BresenhamCircle::new((0, 0), 10).chain(FilledCircle::new((0, 0), 10)) {
+            assert!(disc.contains(&point));
+        }
+    }
+
+    #[test]
+    fn angle_conversions_round_trip() {
+        assert_eq!(Angle::Degrees(90.).to_radians(), FRAC_PI_2);
+        assert_eq!(Angle::Radians(PI).to_degrees(), 180.);
+        for degrees in [-720., -90., 0., 45., 180., 359., 1080.] {
+            let radians = Angle::Degrees(degrees).to_radians();
+            assert!((Angle::Radians(radians).to_degrees() - degrees).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn angle_normalized() {
+        assert_eq!(Angle::Degrees(-90.).normalized(), Angle::Degrees(270.));
+        assert_eq!(Angle::Degrees(720.).normalized(), Angle::Degrees(0.));
+        assert_eq!(Angle::Degrees(359.).normalized(), Angle::Degrees(359.));
+        assert_eq!(
+            Angle::Radians(-FRAC_PI_2).normalized(),
+            Angle::Radians(1.5 * PI)
+        );
+        assert_eq!(Angle::Radians(TAU).normalized(), Angle::Radians(0.));
+    }
+
+    #[test]
+    fn angle_arithmetic() {
+        assert_eq!(
+            Angle::Degrees(30.) + Angle::Degrees(60.),
+            Angle::Degrees(90.)
+        );
+        assert_eq!(
+            Angle::Degrees(30.) - Angle::Degrees(60.),
+            Angle::Degrees(-30.)
+        );
+        assert_eq!(
+            Angle::Radians(FRAC_PI_2) + Angle::Degrees(90.),
+            Angle::Radians(PI)
+        );
+        assert_eq!(
+            Angle::Degrees(180.) - Angle::Radians(FRAC_PI_2),
+            Angle::Radians(FRAC_PI_2)
+        );
+    }
 }
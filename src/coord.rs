@@ -0,0 +1,140 @@
+//! An opt-in position type with arithmetic operators, enabled by the `coord` feature.
+//!
+//! With the `serde` feature, [`Coord`] also implements `Serialize` and `Deserialize`, as an
+//! object with named `x` and `y` fields.
+
+use std::ops::{Add, Mul, Sub};
+
+use crate::Point;
+
+/// A position on the grid. Unlike the [`Point`] tuple, it supports arithmetic operators.
+///
+/// The map traits and algorithms keep using [`Point`]: convert back and forth with `From`/`Into`.
+///
+/// # Examples
+/// ```
+/// use torchbearer::{coord::Coord, Point};
+///
+/// let a = Coord::new(1, 2);
+/// let b: Coord = (4, 6).into();
+///
+/// assert_eq!(b - a, Coord::new(3, 4));
+/// assert_eq!((b - a).manhattan(), 7);
+/// assert_eq!((b - a).length(), 5.);
+///
+/// let point: Point = (a + b * 2).into();
+/// assert_eq!(point, (9, 14));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Coord {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Coord {
+    pub fn new(x: i32, y: i32) -> Self {
+        Coord { x, y }
+    }
+
+    /// The manhattan length of this coordinate, seen as a vector from `(0, 0)`: `|x| + |y|`.
+    /// To get the distance between two coordinates, use `(a - b).manhattan()`.
+    pub fn manhattan(&self) -> i32 {
+        self.x.abs() + self.y.abs()
+    }
+
+    /// The Chebyshev length of this coordinate, seen as a vector from `(0, 0)`: `max(|x|, |y|)`.
+    pub fn chebyshev(&self) -> i32 {
+        self.x.abs().max(self.y.abs())
+    }
+
+    /// The euclidean length of this coordinate, seen as a vector from `(0, 0)`.
+    pub fn length(&self) -> f32 {
+        ((self.x * self.x + self.y * self.y) as f32).sqrt()
+    }
+}
+
+impl From<Point> for Coord {
+    fn from((x, y): Point) -> Self {
+        Coord { x, y }
+    }
+}
+
+impl From<Coord> for Point {
+    fn from(coord: Coord) -> Self {
+        (coord.x, coord.y)
+    }
+}
+
+impl Add for Coord {
+    type Output = Coord;
+
+    fn add(self, other: Coord) -> Coord {
+        Coord::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Coord {
+    type Output = Coord;
+
+    fn sub(self, other: Coord) -> Coord {
+        Coord::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Mul<i32> for Coord {
+    type Output = Coord;
+
+    fn mul(self, factor: i32) -> Coord {
+        Coord::new(self.x * factor, self.y * factor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Point;
+
+    use super::Coord;
+
+    #[test]
+    fn coord_operators() {
+        let a = Coord::new(3, -2);
+        let b = Coord::new(-1, 5);
+
+        assert_eq!(a + b, Coord::new(2, 3));
+        assert_eq!(a - b, Coord::new(4, -7));
+        assert_eq!(a * 3, Coord::new(9, -6));
+    }
+
+    #[test]
+    fn coord_lengths() {
+        let coord = Coord::new(-3, 4);
+
+        assert_eq!(coord.manhattan(), 7);
+        assert_eq!(coord.chebyshev(), 4);
+        assert_eq!(coord.length(), 5.);
+    }
+
+    #[test]
+    fn coord_point_round_trip() {
+        let point: Point = (7, -1);
+
+        let coord: Coord = point.into();
+        assert_eq!(coord, Coord::new(7, -1));
+
+        let back: Point = coord.into();
+        assert_eq!(back, point);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn coord_serializes_as_object() {
+        let path: Vec<Coord> = vec![(1, 2).into(), (2, 3).into()];
+
+        let json = serde_json::to_string(&path).unwrap();
+        assert_eq!(json, r#"[{"x":1,"y":2},{"x":2,"y":3}]"#);
+
+        let parsed: Vec<Coord> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, path);
+    }
+}
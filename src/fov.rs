@@ -1,8 +1,10 @@
 //! Collection of utility function to calculate field of vision.
 
+use std::{collections::HashSet, f32::consts::TAU};
+
 use crate::{
-    bresenham::{BresenhamLine, ThickBresenhamCircle},
-    Point,
+    bresenham::{is_angle_between, Angle, BresenhamLine, ThickBresenhamCircle},
+    Direction, OutOfBounds, Point,
 };
 
 /// Implement the VisionMap trait to use the field of view function.
@@ -82,12 +84,82 @@ pub trait VisionMap {
 /// }
 /// ```
 pub fn field_of_view<T: VisionMap>(map: &T, from: Point, radius: i32) -> Vec<(i32, i32)> {
+    try_field_of_view(map, from, radius).unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Same as [`field_of_view`], but returns an error instead of panicking if `from` is out of
+/// the map bounds.
+///
+/// # Arguments
+///
+/// * `map` - A struct implementing the `VisionMap` trait.
+/// * `from` - The origin/center of the field of vision.
+/// * `radius` - How far the vision should go.
+///
+/// # Examples
+/// ```
+/// use torchbearer::{fov::try_field_of_view, map::Grid};
+///
+/// let map = Grid::new(10, 10);
+///
+/// assert_eq!(try_field_of_view(&map, (3, 3), 0), Ok(vec![(3, 3)]));
+/// assert!(try_field_of_view(&map, (3, -1), 5).is_err());
+/// ```
+pub fn try_field_of_view<T: VisionMap>(
+    map: &T,
+    from: Point,
+    radius: i32,
+) -> Result<Vec<Point>, OutOfBounds> {
+    OutOfBounds::check(from, map.dimensions())?;
     let (x, y) = from;
-    assert_in_bounds(map, x, y);
 
-    if radius < 1 {
-        return vec![(x, y)];
-    }
+    Ok(if radius < 1 {
+        vec![(x, y)]
+    } else if radius <= 2 {
+        small_field_of_view(map, from, radius)
+    } else {
+        raycast_field_of_view(map, from, radius)
+    })
+}
+
+/// The perimeter of `ThickBresenhamCircle::new((0, 0), 1)`.
+const RADIUS_1_PERIMETER: [Point; 8] = [
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+];
+
+/// The perimeter of `ThickBresenhamCircle::new((0, 0), 2)`.
+const RADIUS_2_PERIMETER: [Point; 16] = [
+    (2, -1),
+    (2, 0),
+    (2, 1),
+    (1, 2),
+    (0, 2),
+    (-1, 2),
+    (-2, 1),
+    (-2, 0),
+    (-2, -1),
+    (-1, -2),
+    (0, -2),
+    (1, -2),
+    (1, 1),
+    (-1, 1),
+    (-1, -1),
+    (1, -1),
+];
+
+/// Field of view for a radius of 1 or 2. Casts the same rays as [`raycast_field_of_view`], but
+/// with a precomputed perimeter and a fixed size grid: for such small areas, the setup cost of
+/// the general case dominates the actual work.
+fn small_field_of_view<T: VisionMap>(map: &T, (x, y): Point, radius: i32) -> Vec<Point> {
+    const SIZE: i32 = 5;
+    const CENTER: i32 = 2;
 
     let (width, height) = map.dimensions();
 
@@ -101,23 +173,496 @@ pub fn field_of_view<T: VisionMap>(map: &T, from: Point, radius: i32) -> Vec<(i3
         return vec![];
     }
 
-    let (sub_width, sub_height) = (maxx - minx + 1, maxy - miny + 1);
-    let (offset_x, offset_y) = (minx, miny);
+    let mut visibles = [false; (SIZE * SIZE) as usize];
+    visibles[(CENTER + CENTER * SIZE) as usize] = true;
 
-    let mut visibles = vec![false; (sub_width * sub_height) as usize];
+    let perimeter: &[Point] = if radius == 1 {
+        &RADIUS_1_PERIMETER
+    } else {
+        &RADIUS_2_PERIMETER
+    };
+    for &(dx, dy) in perimeter {
+        // We skip the first item as it is the origin position.
+        for (ray_x, ray_y) in BresenhamLine::new((x, y), (x + dx, y + dy)).skip(1) {
+            if ray_x < minx || ray_y < miny || ray_x > maxx || ray_y > maxy {
+                break;
+            }
+            visibles[(ray_x - x + CENTER + (ray_y - y + CENTER) * SIZE) as usize] = true;
+
+            if !map.is_transparent((ray_x, ray_y)) {
+                break;
+            }
+        }
+    }
+
+    let mut visible_positions = Vec::with_capacity(visibles.len());
+    for visible_y in miny..=maxy {
+        for visible_x in minx..=maxx {
+            if visibles[(visible_x - x + CENTER + (visible_y - y + CENTER) * SIZE) as usize] {
+                visible_positions.push((visible_x, visible_y));
+            }
+        }
+    }
+    visible_positions
+}
+
+/// The general case of the field of view, casting rays from the center to a circle.
+fn raycast_field_of_view<T: VisionMap>(map: &T, from: Point, radius: i32) -> Vec<Point> {
+    raycast_window(map, from, radius, ThickBresenhamCircle::new(from, radius))
+        .into_positions()
+        .collect()
+}
+
+/// Cast the rays from the center to each point of the perimeter, marking the visible tiles in
+/// the smallest window containing the square of side `2 * radius + 1`.
+fn raycast_window<T, I>(map: &T, from: Point, radius: i32, perimeter: I) -> VisibleWindow
+where
+    T: VisionMap,
+    I: IntoIterator<Item = Point>,
+{
+    let window = match RayWindow::new(map, from, radius) {
+        Some(window) => window,
+        None => {
+            return VisibleWindow {
+                visibles: vec![],
+                width: 1,
+                offset: (0, 0),
+            }
+        }
+    };
+
+    let mut visibles = vec![false; window.len()];
+    cast_rays(map, from, perimeter, &mut visibles, &window);
+
+    VisibleWindow {
+        visibles,
+        width: window.width,
+        offset: window.offset,
+    }
+}
+
+/// The part of the map the rays of a field of view are cast in: the square of side
+/// `2 * radius + 1` around the origin, clipped to the map.
+struct RayWindow {
+    width: i32,
+    height: i32,
+    offset: Point,
+}
+
+impl RayWindow {
+    /// The window around `from`, or `None` if there is no area to check.
+    fn new<T: VisionMap>(map: &T, (x, y): Point, radius: i32) -> Option<Self> {
+        let (width, height) = map.dimensions();
+
+        let minx = (x - radius).max(0);
+        let miny = (y - radius).max(0);
+        let maxx = (x + radius).min(width - 1);
+        let maxy = (y + radius).min(height - 1);
+
+        if maxx - minx == 0 || maxy - miny == 0 {
+            // Well, no area to check.
+            return None;
+        }
+
+        Some(RayWindow {
+            width: maxx - minx + 1,
+            height: maxy - miny + 1,
+            offset: (minx, miny),
+        })
+    }
+
+    /// The whole map, for buffers indexed by `x + y * width`.
+    fn whole_map<T: VisionMap>(map: &T) -> Self {
+        let (width, height) = map.dimensions();
+        RayWindow {
+            width,
+            height,
+            offset: (0, 0),
+        }
+    }
+
+    fn len(&self) -> usize {
+        (self.width * self.height) as usize
+    }
+
+    fn contains(&self, (x, y): Point) -> bool {
+        let (offset_x, offset_y) = self.offset;
+        x >= offset_x && y >= offset_y && x < offset_x + self.width && y < offset_y + self.height
+    }
+
+    fn index(&self, (x, y): Point) -> usize {
+        (x - self.offset.0 + (y - self.offset.1) * self.width) as usize
+    }
+}
+
+/// Mark the origin as visible in `visibles`, then cast the rays from the origin to each point of
+/// the perimeter. `visibles` holds one boolean per tile of `window`.
+fn cast_rays<T, I>(map: &T, from: Point, perimeter: I, visibles: &mut [bool], window: &RayWindow)
+where
+    T: VisionMap,
+    I: IntoIterator<Item = Point>,
+{
     // Set origin as visible.
-    visibles[(x - offset_x + (y - offset_y) * sub_width) as usize] = true;
+    visibles[window.index(from)] = true;
 
-    for point in ThickBresenhamCircle::new(from, radius) {
+    for point in perimeter {
         cast_ray(
             map,
-            &mut visibles,
-            sub_width,
-            sub_height,
+            visibles,
+            window.width,
+            window.height,
             from,
             point,
-            (offset_x, offset_y),
+            window.offset,
+        );
+    }
+}
+
+/// A window of the map, with the visibility of each of its tiles.
+struct VisibleWindow {
+    visibles: Vec<bool>,
+    width: i32,
+    offset: Point,
+}
+
+impl VisibleWindow {
+    /// The positions of the visible tiles, in row-major order.
+    fn into_positions(self) -> impl Iterator<Item = Point> {
+        let VisibleWindow {
+            visibles,
+            width,
+            offset: (offset_x, offset_y),
+        } = self;
+
+        visibles
+            .into_iter()
+            .enumerate()
+            .filter_map(move |(index, visible)| {
+                if visible {
+                    Some((
+                        index as i32 % width + offset_x,
+                        index as i32 / width + offset_y,
+                    ))
+                } else {
+                    None
+                }
+            })
+    }
+}
+
+/// Same as [`field_of_view`], but returns an iterator over the visible positions, in row-major
+/// order, instead of collecting them in a vector. Handy to filter them further without
+/// allocating an intermediate vector.
+///
+/// # Arguments
+///
+/// * `map` - A struct implementing the `VisionMap` trait.
+/// * `from` - The origin/center of the field of vision.
+/// * `radius` - How far the vision should go.
+///
+/// # Panics
+///
+/// Panics if `from` is out of the map bounds.
+pub fn field_of_view_iter<T: VisionMap>(
+    map: &T,
+    from: Point,
+    radius: i32,
+) -> impl Iterator<Item = Point> {
+    let (x, y) = from;
+    assert_in_bounds(map, x, y);
+
+    let window = if radius < 1 {
+        VisibleWindow {
+            visibles: vec![true],
+            width: 1,
+            offset: from,
+        }
+    } else {
+        raycast_window(map, from, radius, ThickBresenhamCircle::new(from, radius))
+    };
+
+    window.into_positions()
+}
+
+/// The shape of the area covered by a field of view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RadiusShape {
+    /// Tiles within an euclidean distance, like [`field_of_view`].
+    Circle,
+    /// Tiles within a Chebyshev distance: diagonal moves count as one step.
+    Square,
+    /// Tiles within a Manhattan distance: diagonal moves count as two steps.
+    Diamond,
+}
+
+/// Same as [`field_of_view`], but the covered area can be a circle, a square or a diamond.
+///
+/// # Arguments
+///
+/// * `map` - A struct implementing the `VisionMap` trait.
+/// * `from` - The origin/center of the field of vision.
+/// * `radius` - How far the vision should go, as a distance matching the `shape`.
+/// * `shape` - The shape of the covered area.
+///
+/// # Panics
+///
+/// Panics if `from` is out of the map bounds.
+pub fn field_of_view_shaped<T: VisionMap>(
+    map: &T,
+    from: Point,
+    radius: i32,
+    shape: RadiusShape,
+) -> Vec<Point> {
+    if shape == RadiusShape::Circle {
+        return field_of_view(map, from, radius);
+    }
+
+    let (x, y) = from;
+    assert_in_bounds(map, x, y);
+
+    if radius < 1 {
+        return vec![(x, y)];
+    }
+
+    // Rays to every tile on the border of the square cover the whole square.
+    let square_perimeter = (-radius..radius).flat_map(|offset| {
+        [
+            (x + offset, y - radius),
+            (x + radius, y + offset),
+            (x - offset, y + radius),
+            (x - radius, y - offset),
+        ]
+    });
+    let positions = raycast_window(map, from, radius, square_perimeter).into_positions();
+
+    match shape {
+        RadiusShape::Diamond => positions
+            .filter(|&(visible_x, visible_y)| {
+                (visible_x - x).abs() + (visible_y - y).abs() <= radius
+            })
+            .collect(),
+        _ => positions.collect(),
+    }
+}
+
+/// Same as [`field_of_view`], but writes the visibility of each tile of the map into `out`,
+/// indexed by `x + y * width`, instead of allocating a vector of positions. `out` is cleared
+/// first.
+///
+/// # Arguments
+///
+/// * `map` - A struct implementing the `VisionMap` trait.
+/// * `from` - The origin/center of the field of vision.
+/// * `radius` - How far the vision should go.
+/// * `out` - One boolean per tile of the map, set to `true` for visible tiles.
+///
+/// # Panics
+///
+/// Panics if `from` is out of the map bounds, or if `out` doesn't contain exactly
+/// `width * height` elements.
+pub fn field_of_view_into<T: VisionMap>(map: &T, from: Point, radius: i32, out: &mut [bool]) {
+    let (x, y) = from;
+    assert_in_bounds(map, x, y);
+    let (width, height) = map.dimensions();
+    if out.len() != (width * height) as usize {
+        panic!(
+            "out should contain {} elements for a map of ({}, {}), got {}.",
+            width * height,
+            width,
+            height,
+            out.len()
+        );
+    }
+
+    out.fill(false);
+
+    if radius < 1 {
+        out[(x + y * width) as usize] = true;
+        return;
+    }
+    if RayWindow::new(map, from, radius).is_none() {
+        return;
+    }
+
+    // The rays never go further than the radius, so the whole map can be used as window.
+    let perimeter = ThickBresenhamCircle::new(from, radius);
+    cast_rays(map, from, perimeter, out, &RayWindow::whole_map(map));
+}
+
+/// Reusable buffers for [`field_of_view`], to compute a field of view every turn without
+/// allocating each time.
+///
+/// # Examples
+/// ```
+/// use torchbearer::{
+///     fov::FovContext,
+///     map::{grid_map, CellMeaning},
+/// };
+///
+/// let map = grid_map(20, 20, vec![true; 400], CellMeaning::Open);
+/// let mut context = FovContext::new();
+///
+/// for x in 0..20 {
+///     let visible_positions = context.compute(&map, (x, 10), 5);
+///     assert!(visible_positions.contains(&(x, 10)));
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FovContext {
+    visibles: Vec<bool>,
+    positions: Vec<Point>,
+}
+
+impl FovContext {
+    /// Creates a context with empty buffers, that grow with the first computations.
+    pub fn new() -> Self {
+        FovContext::default()
+    }
+
+    /// Same as [`field_of_view`], reusing the buffers of the context. Once the buffers are big
+    /// enough for the radius, computing doesn't allocate anymore.
+    ///
+    /// # Arguments
+    ///
+    /// * `map` - A struct implementing the `VisionMap` trait.
+    /// * `from` - The origin/center of the field of vision.
+    /// * `radius` - How far the vision should go.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from` is out of the map bounds.
+    pub fn compute<T: VisionMap>(&mut self, map: &T, from: Point, radius: i32) -> &[Point] {
+        let (x, y) = from;
+        assert_in_bounds(map, x, y);
+        self.positions.clear();
+
+        if radius < 1 {
+            self.positions.push(from);
+            return &self.positions;
+        }
+
+        let window = match RayWindow::new(map, from, radius) {
+            Some(window) => window,
+            None => return &self.positions,
+        };
+
+        self.visibles.clear();
+        self.visibles.resize(window.len(), false);
+        let perimeter = ThickBresenhamCircle::new(from, radius);
+        cast_rays(map, from, perimeter, &mut self.visibles, &window);
+
+        let (offset_x, offset_y) = window.offset;
+        self.positions.extend(
+            self.visibles
+                .iter()
+                .enumerate()
+                .filter(|(_, &visible)| visible)
+                .map(|(index, _)| {
+                    (
+                        index as i32 % window.width + offset_x,
+                        index as i32 / window.width + offset_y,
+                    )
+                }),
         );
+        &self.positions
+    }
+}
+
+/// Field of view using symmetric shadowcasting: if a tile `b` is visible from `a`, then `a` is
+/// visible from `b`. The original [`field_of_view`] casts rays, which is not symmetric.
+///
+/// The area around `from` is split in four quadrants, each scanned row by row, going away from
+/// the origin. Opaque tiles cast shadows on the following rows, and a transparent tile is only
+/// visible if its center is in the light. Opaque tiles are visible as long as any part of them
+/// is lit. Tiles outside of the map are considered opaque.
+/// Based on Albert Ford's <https://www.albertford.com/shadowcasting/>.
+///
+/// Returns the visible positions in row-major order, each position once, including `from`.
+///
+/// # Arguments
+///
+/// * `map` - A struct implementing the `VisionMap` trait.
+/// * `from` - The origin/center of the field of vision.
+/// * `radius` - How far the vision should go, as an euclidean distance.
+///
+/// # Panics
+///
+/// Panics if `from` is out of the map bounds.
+pub fn field_of_view_shadowcasting<T: VisionMap>(map: &T, from: Point, radius: i32) -> Vec<Point> {
+    let (x, y) = from;
+    assert_in_bounds(map, x, y);
+
+    if radius < 1 {
+        return vec![(x, y)];
+    }
+
+    let (width, height) = map.dimensions();
+    let (min_x, min_y) = ((x - radius).max(0), (y - radius).max(0));
+    let (max_x, max_y) = ((x + radius).min(width - 1), (y + radius).min(height - 1));
+    let (sub_width, sub_height) = (max_x - min_x + 1, max_y - min_y + 1);
+    let mut visibles = vec![false; (sub_width * sub_height) as usize];
+
+    let mut reveal = |(tile_x, tile_y): Point| {
+        let (dx, dy) = (tile_x - x, tile_y - y);
+        if dx * dx + dy * dy <= radius * radius && !is_out_of_bounds(map, tile_x, tile_y) {
+            visibles[((tile_x - min_x) + (tile_y - min_y) * sub_width) as usize] = true;
+        }
+    };
+    reveal(from);
+
+    for quadrant in [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ] {
+        let transform = |(depth, column): (i32, i32)| match quadrant {
+            Direction::North => (x + column, y - depth),
+            Direction::South => (x + column, y + depth),
+            Direction::East => (x + depth, y + column),
+            _ => (x - depth, y + column),
+        };
+        let is_wall = |tile: (i32, i32)| {
+            let (tile_x, tile_y) = transform(tile);
+            is_out_of_bounds(map, tile_x, tile_y) || !map.is_transparent((tile_x, tile_y))
+        };
+
+        let mut rows = vec![ShadowRow {
+            depth: 1,
+            start_slope: (-1, 1),
+            end_slope: (1, 1),
+        }];
+        while let Some(mut row) = rows.pop() {
+            if row.depth > radius {
+                continue;
+            }
+
+            let mut previous_is_wall = None;
+            for column in row.min_column()..=row.max_column() {
+                let tile = (row.depth, column);
+                let tile_is_wall = is_wall(tile);
+                if tile_is_wall || row.is_symmetric(column) {
+                    reveal(transform(tile));
+                }
+                if previous_is_wall == Some(true) && !tile_is_wall {
+                    row.start_slope = ShadowRow::slope(tile);
+                }
+                if previous_is_wall == Some(false) && tile_is_wall {
+                    rows.push(ShadowRow {
+                        depth: row.depth + 1,
+                        start_slope: row.start_slope,
+                        end_slope: ShadowRow::slope(tile),
+                    });
+                }
+                previous_is_wall = Some(tile_is_wall);
+            }
+            if previous_is_wall == Some(false) {
+                rows.push(ShadowRow {
+                    depth: row.depth + 1,
+                    ..row
+                });
+            }
+        }
     }
 
     visibles
@@ -126,8 +671,8 @@ pub fn field_of_view<T: VisionMap>(map: &T, from: Point, radius: i32) -> Vec<(i3
         .filter_map(|(index, visible)| {
             if visible {
                 Some((
-                    index as i32 % sub_width + offset_x,
-                    index as i32 / sub_width + offset_y,
+                    index as i32 % sub_width + min_x,
+                    index as i32 / sub_width + min_y,
                 ))
             } else {
                 None
@@ -136,28 +681,1039 @@ pub fn field_of_view<T: VisionMap>(map: &T, from: Point, radius: i32) -> Vec<(i3
         .collect()
 }
 
-fn is_out_of_bounds<M: VisionMap>(map: &M, x: i32, y: i32) -> bool {
+/// Field of view using restrictive precise angle shadowcasting (RPAS): instead of casting rays,
+/// each octant around `from` is scanned row by row, going away from the origin, keeping track
+/// of the angles hidden by the opaque tiles met so far.
+///
+/// A tile of a row covers the angles between its near and far edges. It is visible if its
+/// center is not hidden, and at least one of its edges isn't either. This gives clean shadows
+/// behind pillars, without the blind spots rays can leave close to walls. Tiles outside of the
+/// map are considered opaque.
+/// Based on <http://www.roguebasin.com/index.php/Restrictive_Precise_Angle_Shadowcasting>.
+///
+/// Returns the visible positions in row-major order, each position once, including `from`.
+///
+/// # Arguments
+///
+/// * `map` - A struct implementing the `VisionMap` trait.
+/// * `from` - The origin/center of the field of vision.
+/// * `radius` - How far the vision should go, as an euclidean distance.
+///
+/// # Panics
+///
+/// Panics if `from` is out of the map bounds.
+pub fn field_of_view_rpas<T: VisionMap>(map: &T, from: Point, radius: i32) -> Vec<Point> {
+    let (x, y) = from;
+    assert_in_bounds(map, x, y);
+
+    if radius < 1 {
+        return vec![(x, y)];
+    }
+
     let (width, height) = map.dimensions();
-    x < 0 || y < 0 || x >= width || y >= height
+    let (min_x, min_y) = ((x - radius).max(0), (y - radius).max(0));
+    let (max_x, max_y) = ((x + radius).min(width - 1), (y + radius).min(height - 1));
+    let sub_width = max_x - min_x + 1;
+    let mut visibles = vec![false; (sub_width * (max_y - min_y + 1)) as usize];
+    visibles[((x - min_x) + (y - min_y) * sub_width) as usize] = true;
+
+    // How the column and the depth of an octant translate to x and y.
+    const OCTANTS: [(i32, i32, i32, i32); 8] = [
+        (1, 0, 0, 1),
+        (0, 1, 1, 0),
+        (0, -1, 1, 0),
+        (-1, 0, 0, 1),
+        (-1, 0, 0, -1),
+        (0, -1, -1, 0),
+        (0, 1, -1, 0),
+        (1, 0, 0, -1),
+    ];
+
+    for (column_x, depth_x, column_y, depth_y) in OCTANTS {
+        // Hidden angles, from 0.0 along the axis to 1.0 along the diagonal, sorted and merged.
+        let mut obstructions: Vec<(f32, f32)> = vec![];
+        for depth in 1..=radius {
+            let tile_angle = 1. / (depth + 1) as f32;
+            let mut row_obstructions = vec![];
+            for column in 0..=depth {
+                let (tile_x, tile_y) = (
+                    x + column * column_x + depth * depth_x,
+                    y + column * column_y + depth * depth_y,
+                );
+                let near = column as f32 * tile_angle;
+                let center = near + tile_angle / 2.;
+                let far = near + tile_angle;
+
+                let is_lit = |angle: f32| {
+                    !obstructions
+                        .iter()
+                        .any(|&(start, end)| start <= angle && angle <= end)
+                };
+                let is_center_lit = !obstructions
+                    .iter()
+                    .any(|&(start, end)| start < center && center < end);
+
+                let is_out = is_out_of_bounds(map, tile_x, tile_y);
+                if !is_out
+                    && is_center_lit
+                    && (is_lit(near) || is_lit(far))
+                    && column * column + depth * depth <= radius * radius
+                {
+                    visibles[((tile_x - min_x) + (tile_y - min_y) * sub_width) as usize] = true;
+                }
+                if is_out || !map.is_transparent((tile_x, tile_y)) {
+                    row_obstructions.push((near, far));
+                }
+            }
+
+            // Tiles of a row don't hide each other, the obstructions only apply to the next rows.
+            obstructions.extend(row_obstructions);
+            obstructions.sort_by(|a, b| a.0.total_cmp(&b.0));
+            let mut merged: Vec<(f32, f32)> = Vec::with_capacity(obstructions.len());
+            for (start, end) in obstructions {
+                match merged.last_mut() {
+                    Some(last) if start <= last.1 => last.1 = last.1.max(end),
+                    _ => merged.push((start, end)),
+                }
+            }
+            obstructions = merged;
+
+            if obstructions == [(0., 1.)] {
+                // The whole octant is in the dark.
+                break;
+            }
+        }
+    }
+
+    VisibleWindow {
+        visibles,
+        width: sub_width,
+        offset: (min_x, min_y),
+    }
+    .into_positions()
+    .collect()
 }
 
-fn assert_in_bounds<M: VisionMap>(map: &M, x: i32, y: i32) {
-    let (width, height) = map.dimensions();
-    if is_out_of_bounds(map, x, y) {
+/// Permissive field of view: a tile is visible if any line going from the tile of `from` to
+/// that tile is unobstructed, which lets you peek around corners more than [`field_of_view`].
+///
+/// `permissiveness` goes from `0` to `8`. At `0`, only the line going from the center of
+/// `from` to the center of the tile is checked. Each step widens the area of both tiles the
+/// lines can start and end from, up to the whole tiles at `8`. Lines only touching the corner
+/// of an opaque tile are not obstructed.
+///
+/// Returns the visible positions in row-major order, including `from`.
+///
+/// # Arguments
+///
+/// * `map` - A struct implementing the `VisionMap` trait.
+/// * `from` - The origin/center of the field of vision.
+/// * `radius` - How far the vision should go, as an euclidean distance.
+/// * `permissiveness` - How wide the lines can spread, from `0` to `8`.
+///
+/// # Panics
+///
+/// Panics if `from` is out of the map bounds, or if `permissiveness` is higher than `8`.
+pub fn field_of_view_permissive<T: VisionMap>(
+    map: &T,
+    from: Point,
+    radius: i32,
+    permissiveness: u8,
+) -> Vec<Point> {
+    let (x, y) = from;
+    assert_in_bounds(map, x, y);
+    if permissiveness > 8 {
         panic!(
-            "(x, y) should be between (0,0) and ({}, {}), got ({}, {}).",
-            width, height, x, y
+            "permissiveness should be between 0 and 8, got {}.",
+            permissiveness
         );
     }
-}
 
-fn cast_ray<T: VisionMap>(
-    map: &T,
-    visibles: &mut [bool],
-    width: i32,
-    height: i32,
-    origin: Point,
-    destination: Point,
+    // The sample points stay strictly inside their tile.
+    let spread = 0.499 * permissiveness as f32 / 8.;
+    let offsets: Vec<(f32, f32)> = if permissiveness == 0 {
+        vec![(0., 0.)]
+    } else {
+        let steps = [-spread, 0., spread];
+        steps
+            .iter()
+            .flat_map(|&offset_y| steps.iter().map(move |&offset_x| (offset_x, offset_y)))
+            .collect()
+    };
+
+    let (width, height) = map.dimensions();
+    let radius = radius.max(0);
+    let mut visibles = vec![];
+    for tile_y in (y - radius).max(0)..=(y + radius).min(height - 1) {
+        for tile_x in (x - radius).max(0)..=(x + radius).min(width - 1) {
+            let (dx, dy) = (tile_x - x, tile_y - y);
+            if dx * dx + dy * dy > radius * radius {
+                continue;
+            }
+
+            let is_visible = offsets.iter().any(|&(from_x, from_y)| {
+                offsets.iter().any(|&(to_x, to_y)| {
+                    is_segment_clear(
+                        map,
+                        (x as f32 + from_x, y as f32 + from_y),
+                        (tile_x as f32 + to_x, tile_y as f32 + to_y),
+                    )
+                })
+            });
+            if is_visible {
+                visibles.push((tile_x, tile_y));
+            }
+        }
+    }
+    visibles
+}
+
+/// Field of view split into distance bands, computed in one pass. Useful for graduated vision,
+/// clear nearby and fuzzy far away.
+///
+/// Returns each visible tile with the index of its band in the sorted `tiers`: a tile at
+/// euclidean distance `d` falls in the first tier whose radius is higher or equal to `d`. The
+/// field of view is computed once, using the biggest tier as radius, and tiles further than it
+/// are left out.
+///
+/// # Arguments
+///
+/// * `map` - A struct implementing the `VisionMap` trait.
+/// * `from` - The origin/center of the field of vision.
+/// * `tiers` - The radius of each band, in any order.
+///
+/// # Panics
+///
+/// Panics if `from` is out of the map bounds.
+///
+/// # Examples
+/// ```
+/// # use torchbearer::{fov::{field_of_view_tiered, VisionMap}, Point};
+/// # struct SampleMap;
+/// # impl VisionMap for SampleMap {
+/// #     fn dimensions(&self) -> (i32, i32) { (20, 20) }
+/// #     fn is_transparent(&self, _: Point) -> bool { true }
+/// # }
+/// # let sample_map = SampleMap;
+/// for ((x, y), tier) in field_of_view_tiered(&sample_map, (10, 10), &[3, 6, 9]) {
+///     // tier 0 is within 3 tiles, tier 1 within 6 tiles, tier 2 within 9 tiles.
+/// }
+/// ```
+pub fn field_of_view_tiered<T: VisionMap>(
+    map: &T,
+    from: Point,
+    tiers: &[i32],
+) -> Vec<(Point, usize)> {
+    let mut tiers = tiers.to_vec();
+    tiers.sort_unstable();
+    let radius = match tiers.last() {
+        Some(&radius) => radius,
+        None => return vec![],
+    };
+
+    field_of_view(map, from, radius)
+        .into_iter()
+        .filter_map(|(x, y)| {
+            let (dx, dy) = (x - from.0, y - from.1);
+            let square_distance = dx * dx + dy * dy;
+            tiers
+                .iter()
+                .position(|&tier| square_distance <= tier * tier)
+                .map(|tier| ((x, y), tier))
+        })
+        .collect()
+}
+
+/// Field of view ignoring the closest tiles, like looking through a scope: returns the visible
+/// tiles whose euclidean distance to `from` is between `min_radius` and `max_radius`, both
+/// included.
+///
+/// Rays still travel through the ignored zone, so walls there still cast their shadow on
+/// further tiles. They are just not reported as visible.
+///
+/// # Arguments
+///
+/// * `map` - A struct implementing the `VisionMap` trait.
+/// * `from` - The origin/center of the field of vision.
+/// * `min_radius` - Tiles closer than this are not reported.
+/// * `max_radius` - How far the vision should go.
+///
+/// # Panics
+///
+/// Panics if `from` is out of the map bounds.
+pub fn field_of_view_ranged<T: VisionMap>(
+    map: &T,
+    from: Point,
+    min_radius: i32,
+    max_radius: i32,
+) -> Vec<Point> {
+    let (min, max) = (min_radius * min_radius, max_radius * max_radius);
+    field_of_view(map, from, max_radius)
+        .into_iter()
+        .filter(|&(x, y)| {
+            let (dx, dy) = (x - from.0, y - from.1);
+            let square_distance = dx * dx + dy * dy;
+            square_distance >= min && square_distance <= max
+        })
+        .collect()
+}
+
+/// Same as [`field_of_view`], but only the tiles within the cone going clockwise from the
+/// `start` angle to the `end` angle are visible. An angle of `0` points east, and a quarter turn
+/// points south, as the y axis points down. The cone can go past east, for instance from `270°`
+/// to `90°` to see everything on the east side. A cone of a full turn or more sees all around,
+/// like [`field_of_view`] with the radius rounded up.
+///
+/// The radius is a float, so that the cone can grow smoothly, for instance to animate a torch
+/// being lit: a tile is visible only if its euclidean distance to `from` is at most `radius`.
+///
+/// # Arguments
+///
+/// * `map` - A struct implementing the `VisionMap` trait.
+/// * `from` - The origin/center of the field of vision.
+/// * `radius` - How far the vision should go.
+/// * `start` - The angle where the cone starts.
+/// * `end` - The angle where the cone ends.
+///
+/// # Panics
+///
+/// Panics if `from` is out of the map bounds.
+///
+/// # Examples
+/// ```
+/// use torchbearer::{
+///     bresenham::Angle,
+///     fov::{cone_of_view, VisionMap},
+/// };
+///
+/// struct SampleMap {
+///     width: i32,
+///     height: i32,
+/// }
+///
+/// impl VisionMap for SampleMap {
+///     fn dimensions(&self) -> (i32, i32) {
+///         (self.width, self.height)
+///     }
+///
+///     fn is_transparent(&self, (x, y): (i32, i32)) -> bool {
+///         true
+///     }
+/// }
+///
+/// let sample_map = SampleMap { width: 40, height: 40 };
+/// let visible = cone_of_view(&sample_map, (20, 20), 10.5, Angle::Degrees(15.), Angle::Degrees(70.));
+///
+/// assert!(visible.contains(&(25, 25)));
+/// assert!(!visible.contains(&(25, 15)));
+/// ```
+pub fn cone_of_view<T: VisionMap>(
+    map: &T,
+    from: Point,
+    radius: f32,
+    start: Angle,
+    end: Angle,
+) -> Vec<Point> {
+    let (start, end) = (start.to_radians(), end.to_radians());
+    if end - start >= TAU {
+        return field_of_view(map, from, radius.ceil() as i32);
+    }
+
+    let square_radius = radius * radius;
+    field_of_view(map, from, radius.ceil() as i32)
+        .into_iter()
+        .filter(|&(x, y)| {
+            let (dx, dy) = ((x - from.0) as f32, (y - from.1) as f32);
+            (dx, dy) == (0., 0.)
+                || (dx * dx + dy * dy <= square_radius
+                    && is_angle_between(dy.atan2(dx), start, end))
+        })
+        .collect()
+}
+
+/// Same as [`field_of_view`], but only the tiles within the cone centered on `facing` and
+/// spanning `arc` are visible, half of it on each side. An angle of `0` points east, and a
+/// quarter turn points south, as the y axis points down. An arc of a full turn or more sees all
+/// around.
+///
+/// Handy to attach the vision to the orientation of an entity, where [`cone_of_view`] takes
+/// the angles where the cone starts and ends.
+///
+/// # Arguments
+///
+/// * `map` - A struct implementing the `VisionMap` trait.
+/// * `from` - The origin/center of the field of vision.
+/// * `radius` - How far the vision should go, as an euclidean distance.
+/// * `facing` - The direction the cone is centered on.
+/// * `arc` - The total width of the cone.
+///
+/// # Panics
+///
+/// Panics if `from` is out of the map bounds.
+pub fn field_of_view_facing<T: VisionMap>(
+    map: &T,
+    from: Point,
+    radius: i32,
+    facing: Angle,
+    arc: Angle,
+) -> Vec<Point> {
+    let (facing, arc) = (facing.to_radians(), arc.to_radians());
+    if arc >= TAU {
+        return field_of_view(map, from, radius);
+    }
+
+    cone_of_view(
+        map,
+        from,
+        radius as f32,
+        Angle::Radians(facing - arc / 2.),
+        Angle::Radians(facing + arc / 2.),
+    )
+}
+
+/// Same as [`field_of_view`], but the opaque tiles are left out: walls reached by the rays
+/// are not reported as visible, only the tiles that can be seen through are.
+///
+/// # Arguments
+///
+/// * `map` - A struct implementing the `VisionMap` trait.
+/// * `from` - The origin/center of the field of vision.
+/// * `radius` - How far the vision should go.
+///
+/// # Panics
+///
+/// Panics if `from` is out of the map bounds.
+pub fn field_of_view_transparent_only<T: VisionMap>(
+    map: &T,
+    from: Point,
+    radius: i32,
+) -> Vec<Point> {
+    field_of_view(map, from, radius)
+        .into_iter()
+        .filter(|&position| map.is_transparent(position))
+        .collect()
+}
+
+/// Same as [`field_of_view`], but each visible tile comes with its euclidean distance to
+/// `from`, for instance to dim a light with the distance. `from` itself is at a distance of
+/// `0.0`.
+///
+/// # Arguments
+///
+/// * `map` - A struct implementing the `VisionMap` trait.
+/// * `from` - The origin/center of the field of vision.
+/// * `radius` - How far the vision should go.
+///
+/// # Panics
+///
+/// Panics if `from` is out of the map bounds.
+pub fn field_of_view_with_distance<T: VisionMap>(
+    map: &T,
+    from: Point,
+    radius: i32,
+) -> Vec<(Point, f32)> {
+    field_of_view(map, from, radius)
+        .into_iter()
+        .map(|(x, y)| {
+            let (dx, dy) = ((x - from.0) as f32, (y - from.1) as f32);
+            ((x, y), (dx * dx + dy * dy).sqrt())
+        })
+        .collect()
+}
+
+/// How the intensity of a light decreases with the distance, see [`light_map`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Falloff {
+    /// The intensity decreases at a constant rate.
+    Linear,
+    /// The intensity decreases slowly at first, then quickly.
+    Quadratic,
+    /// The intensity decreases quickly at first, then slowly, like a real light.
+    InverseSquare,
+}
+
+impl Falloff {
+    /// The intensity, between `0.0` and `1.0`, at a `distance` from a light reaching `radius`.
+    fn intensity(self, distance: f32, radius: f32) -> f32 {
+        let ratio = (distance / radius).clamp(0., 1.);
+        match self {
+            Falloff::Linear => 1. - ratio,
+            Falloff::Quadratic => (1. - ratio) * (1. - ratio),
+            Falloff::InverseSquare => {
+                // Shifted and scaled so that the light fades to 0.0 at the radius.
+                let at_radius = 1. / (1. + radius * radius);
+                let intensity = 1. / (1. + distance * distance);
+                ((intensity - at_radius) / (1. - at_radius)).clamp(0., 1.)
+            }
+        }
+    }
+}
+
+/// Compute the light level of each tile lit by a light source at `from`, with an intensity
+/// between `1.0` at the source and `0.0` at `radius`.
+///
+/// # Arguments
+///
+/// * `map` - A struct implementing the `VisionMap` trait.
+/// * `from` - The position of the light source.
+/// * `radius` - How far the light should go.
+/// * `falloff` - How the intensity decreases with the distance.
+///
+/// # Panics
+///
+/// Panics if `from` is out of the map bounds.
+///
+/// # Examples
+/// ```
+/// use torchbearer::fov::{light_map, Falloff, VisionMap};
+///
+/// struct SampleMap {
+///     width: i32,
+///     height: i32,
+/// }
+///
+/// impl VisionMap for SampleMap {
+///     fn dimensions(&self) -> (i32, i32) {
+///         (self.width, self.height)
+///     }
+///
+///     fn is_transparent(&self, (x, y): (i32, i32)) -> bool {
+///         true
+///     }
+/// }
+///
+/// let sample_map = SampleMap { width: 20, height: 20 };
+/// let lights = light_map(&sample_map, (10, 10), 5, Falloff::Linear);
+///
+/// assert!(lights.contains(&((10, 10), 1.0)));
+/// assert!(lights.contains(&((15, 10), 0.0)));
+/// ```
+pub fn light_map<T: VisionMap>(
+    map: &T,
+    from: Point,
+    radius: i32,
+    falloff: Falloff,
+) -> Vec<(Point, f32)> {
+    if radius < 1 {
+        return field_of_view_with_distance(map, from, radius)
+            .into_iter()
+            .map(|(position, _)| (position, 1.))
+            .collect();
+    }
+
+    field_of_view_with_distance(map, from, radius)
+        .into_iter()
+        .map(|(position, distance)| (position, falloff.intensity(distance, radius as f32)))
+        .collect()
+}
+
+/// Combine the light maps of several light sources, as returned by [`light_map`], into a
+/// single map of intensities.
+///
+/// The result contains `width * height` intensities, in row-major order: the intensity of
+/// the tile `(x, y)` is at the index `x + y * width`. Intensities of the same tile are added
+/// up, clamped to `1.0`, and unlit tiles have an intensity of `0.0`.
+///
+/// # Arguments
+///
+/// * `maps` - The light maps to combine.
+/// * `width` - The width of the map.
+/// * `height` - The height of the map.
+///
+/// # Panics
+///
+/// Panics if a lit position is out of the map bounds.
+pub fn combine_light_maps(maps: &[Vec<(Point, f32)>], width: i32, height: i32) -> Vec<f32> {
+    let mut intensities = vec![0.; (width * height) as usize];
+    for &((x, y), intensity) in maps.iter().flatten() {
+        if x < 0 || y < 0 || x >= width || y >= height {
+            panic!(
+                "(x, y) should be between (0,0) and ({}, {}), got ({}, {}).",
+                width, height, x, y
+            );
+        }
+        let combined = &mut intensities[(x + y * width) as usize];
+        *combined = (*combined + intensity).min(1.);
+    }
+    intensities
+}
+
+/// Compute the field of view of several sources at once, for instance all the torches of a
+/// dungeon. Returns one vector per source, in the order of `sources`, each being the result of
+/// [`field_of_view`].
+///
+/// With the `rayon` feature, the sources are computed in parallel. Without it, they are
+/// computed one after the other.
+///
+/// # Arguments
+///
+/// * `map` - A struct implementing the `VisionMap` trait.
+/// * `sources` - The origin and radius of each field of view.
+///
+/// # Panics
+///
+/// Panics if the origin of a source is out of the map bounds.
+///
+/// # Examples
+/// ```
+/// use torchbearer::{fov::field_of_view_many, map::Grid};
+///
+/// let map = Grid::new(20, 20);
+/// let torches = [((2, 2), 3), ((15, 10), 5)];
+///
+/// let lit = field_of_view_many(&map, &torches);
+/// assert_eq!(lit.len(), 2);
+/// assert!(lit[0].contains(&(4, 2)));
+/// assert!(lit[1].contains(&(15, 14)));
+/// ```
+pub fn field_of_view_many<T: VisionMap + Sync>(
+    map: &T,
+    sources: &[(Point, i32)],
+) -> Vec<Vec<Point>> {
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        sources
+            .par_iter()
+            .map(|&(from, radius)| field_of_view(map, from, radius))
+            .collect()
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        sources
+            .iter()
+            .map(|&(from, radius)| field_of_view(map, from, radius))
+            .collect()
+    }
+}
+
+/// Same as [`field_of_view`], but the tiles in `blockers` are considered opaque too, on top
+/// of the opaque tiles of the map. Handy for units or closed doors that block the view
+/// without being part of the map itself.
+///
+/// Like walls, blockers are visible themselves, they only hide what's behind them.
+///
+/// # Arguments
+///
+/// * `map` - A struct implementing the `VisionMap` trait.
+/// * `from` - The origin/center of the field of vision.
+/// * `radius` - How far the vision should go.
+/// * `blockers` - Extra positions considered opaque.
+///
+/// # Panics
+///
+/// Panics if `from` is out of the map bounds.
+pub fn field_of_view_with_blockers<T: VisionMap>(
+    map: &T,
+    from: Point,
+    radius: i32,
+    blockers: &HashSet<Point>,
+) -> Vec<Point> {
+    let map = BlockedVisionMap { map, blockers };
+    field_of_view(&map, from, radius)
+}
+
+/// Same as [`field_of_view`], for a map that wraps around its edges, like a torus: rays
+/// leaving the map through an edge come back from the opposite one.
+///
+/// Returns the visible positions ordered by index `x + y * width`, each one only once, even
+/// when the radius is large enough to see a tile through several edges.
+///
+/// # Arguments
+///
+/// * `map` - A struct implementing the `VisionMap` trait.
+/// * `from` - The origin/center of the field of vision.
+/// * `radius` - How far the vision should go.
+///
+/// # Panics
+///
+/// Panics if `from` is out of the map bounds.
+///
+/// # Examples
+/// ```
+/// use torchbearer::{fov::field_of_view_wrapping, map::Grid};
+///
+/// let map = Grid::new(20, 20);
+/// let visibles = field_of_view_wrapping(&map, (1, 10), 3);
+///
+/// assert!(visibles.contains(&(18, 10)));
+/// ```
+pub fn field_of_view_wrapping<T: VisionMap>(map: &T, from: Point, radius: i32) -> Vec<Point> {
+    let (x, y) = from;
+    assert_in_bounds(map, x, y);
+    let radius = radius.max(0);
+
+    let (width, height) = map.dimensions();
+    let wrapped = WrappingVisionMap {
+        map,
+        offset: (x - radius, y - radius),
+        size: 2 * radius + 1,
+    };
+    let mut visibles = vec![false; (width * height) as usize];
+    for (x, y) in field_of_view(&wrapped, (radius, radius), radius) {
+        let (x, y) = wrapped.to_map_position((x, y));
+        visibles[(x + y * width) as usize] = true;
+    }
+
+    (0..width * height)
+        .filter(|&index| visibles[index as usize])
+        .map(|index| (index % width, index / width))
+        .collect()
+}
+
+/// Find which faces of the visible walls are lit by a light source at `from`, to only draw
+/// those in a 2.5D rendering.
+///
+/// A face of an opaque tile is lit if the tile next to it, in that direction, is visible and
+/// transparent, and if the face is turned towards `from`. Returns each visible opaque tile with
+/// the direction of its lit face, one entry per face. Only cardinal directions are used.
+///
+/// # Arguments
+///
+/// * `map` - A struct implementing the `VisionMap` trait.
+/// * `from` - The position of the light source.
+/// * `radius` - How far the light goes.
+///
+/// # Panics
+///
+/// Panics if `from` is out of the map bounds.
+pub fn lit_wall_faces<T: VisionMap>(map: &T, from: Point, radius: i32) -> Vec<(Point, Direction)> {
+    let (width, height) = map.dimensions();
+    let mut visibles = vec![false; (width * height) as usize];
+    let visible_positions = field_of_view(map, from, radius);
+    for &(x, y) in visible_positions.iter() {
+        visibles[(x + y * width) as usize] = true;
+    }
+
+    let mut faces = vec![];
+    for (x, y) in visible_positions {
+        if map.is_transparent((x, y)) {
+            continue;
+        }
+
+        for direction in Direction::cardinals() {
+            let (dx, dy) = direction.offset();
+            let (nx, ny) = direction.apply((x, y));
+            let facing_light = dx * (from.0 - x) + dy * (from.1 - y) > 0;
+            if facing_light
+                && !is_out_of_bounds(map, nx, ny)
+                && visibles[(nx + ny * width) as usize]
+                && map.is_transparent((nx, ny))
+            {
+                faces.push(((x, y), direction));
+            }
+        }
+    }
+
+    faces
+}
+
+/// Check if `target` is in the field of view from `from`. Gives the same answer as
+/// `field_of_view(map, from, radius).contains(&target)`, but only casts the rays going towards
+/// `target`, which is much cheaper to check a few targets.
+///
+/// # Arguments
+///
+/// * `map` - A struct implementing the `VisionMap` trait.
+/// * `from` - The origin/center of the field of vision.
+/// * `target` - The position to check.
+/// * `radius` - How far the vision should go.
+///
+/// # Panics
+///
+/// Panics if `from` or `target` are out of the map bounds.
+pub fn is_visible<T: VisionMap>(map: &T, from: Point, target: Point, radius: i32) -> bool {
+    let (x, y) = from;
+    assert_in_bounds(map, x, y);
+    assert_in_bounds(map, target.0, target.1);
+
+    if radius < 1 {
+        return target == from;
+    }
+
+    let window = match RayWindow::new(map, from, radius) {
+        Some(window) => window,
+        // Like field_of_view, nothing is visible without area to check.
+        None => return false,
+    };
+
+    // A ray never goes back, so only the ones heading the same way as the target can reach it.
+    let (target_dx, target_dy) = (target.0 - x, target.1 - y);
+    let perimeter = ThickBresenhamCircle::new(from, radius).filter(|&(ray_x, ray_y)| {
+        let (dx, dy) = (ray_x - x, ray_y - y);
+        dx * target_dx >= 0 && dy * target_dy >= 0
+    });
+
+    let mut visibles = vec![false; window.len()];
+    cast_rays(map, from, perimeter, &mut visibles, &window);
+
+    window.contains(target) && visibles[window.index(target)]
+}
+
+/// How well a tile is shielded from a threat, as returned by [`cover_from`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cover {
+    /// The threat can see all the corners of the tile.
+    None,
+    /// The threat can see some of the corners of the tile.
+    Partial,
+    /// The threat can't see any corner of the tile.
+    Full,
+}
+
+/// Find how well `tile` is covered from a `threat`, for instance to pick a position in a
+/// tactical game.
+///
+/// Lines are drawn from the center of the threat's tile to each of the four corners of `tile`,
+/// slightly inside of it. The more lines go through opaque tiles, the better the cover. Going
+/// exactly through the corner of an opaque tile doesn't block a line. A tile further than
+/// `radius` from the threat is out of its sight, and fully covered.
+///
+/// # Arguments
+///
+/// * `map` - A struct implementing the `VisionMap` trait.
+/// * `tile` - The position to check.
+/// * `threat` - The position of the threat.
+/// * `radius` - How far the threat can see.
+///
+/// # Panics
+///
+/// Panics if `tile` or `threat` are out of the map bounds.
+///
+/// # Examples
+/// ```
+/// use torchbearer::{
+///     fov::{cover_from, Cover},
+///     map::Grid,
+/// };
+///
+/// let mut map = Grid::new(10, 10);
+/// map.set_transparent((5, 5), false);
+///
+/// assert_eq!(cover_from(&map, (6, 5), (1, 5), 8), Cover::Full);
+/// assert_eq!(cover_from(&map, (6, 6), (1, 5), 8), Cover::Partial);
+/// assert_eq!(cover_from(&map, (6, 8), (1, 5), 8), Cover::None);
+/// ```
+pub fn cover_from<T: VisionMap>(map: &T, tile: Point, threat: Point, radius: i32) -> Cover {
+    assert_in_bounds(map, tile.0, tile.1);
+    assert_in_bounds(map, threat.0, threat.1);
+
+    let (dx, dy) = (tile.0 - threat.0, tile.1 - threat.1);
+    if dx * dx + dy * dy > radius.max(0) * radius.max(0) {
+        return Cover::Full;
+    }
+
+    let from = (threat.0 as f32, threat.1 as f32);
+    let visible_corners = [(-1., -1.), (1., -1.), (1., 1.), (-1., 1.)]
+        .into_iter()
+        .filter(|&(corner_x, corner_y)| {
+            let corner = (
+                tile.0 as f32 + 0.499 * corner_x,
+                tile.1 as f32 + 0.499 * corner_y,
+            );
+            is_segment_clear(map, from, corner)
+        })
+        .count();
+
+    match visible_corners {
+        0 => Cover::Full,
+        4 => Cover::None,
+        _ => Cover::Partial,
+    }
+}
+
+/// The reverse of a field of view: the tiles from which `target` can be seen, for instance to
+/// know where a guard could stand to spot a sneaking player.
+///
+/// A transparent tile within the euclidean `radius` of `target` can see it if
+/// [`line_of_sight`] going from that tile to `target` is clear. Opaque tiles can't see
+/// anything, and `target` always sees itself. As the lines are followed from the watching
+/// tiles, and a [`BresenhamLine`] isn't always the same both ways, this can differ from the
+/// field of view from `target`.
+///
+/// Returns the positions in row-major order.
+///
+/// # Arguments
+///
+/// * `map` - A struct implementing the `VisionMap` trait.
+/// * `target` - The position to be seen.
+/// * `radius` - How far the tiles seeing `target` can be, as an euclidean distance.
+///
+/// # Panics
+///
+/// Panics if `target` is out of the map bounds.
+pub fn tiles_that_can_see<T: VisionMap>(map: &T, target: Point, radius: i32) -> Vec<Point> {
+    let (x, y) = target;
+    assert_in_bounds(map, x, y);
+
+    let (width, height) = map.dimensions();
+    let radius = radius.max(0);
+    let mut watchers = vec![];
+    for watcher_y in (y - radius).max(0)..=(y + radius).min(height - 1) {
+        for watcher_x in (x - radius).max(0)..=(x + radius).min(width - 1) {
+            let watcher = (watcher_x, watcher_y);
+            let (dx, dy) = (watcher_x - x, watcher_y - y);
+            if watcher == target
+                || (dx * dx + dy * dy <= radius * radius
+                    && map.is_transparent(watcher)
+                    && line_of_sight(map, watcher, target))
+            {
+                watchers.push(watcher);
+            }
+        }
+    }
+    watchers
+}
+
+/// Check if `to` can be seen from `from`, following a [`BresenhamLine`] between the two.
+/// The tiles in between need to be transparent, but `from` and `to` themselves can be opaque:
+/// you can see a wall.
+///
+/// # Panics
+///
+/// Panics if `from` or `to` are out of the map bounds.
+pub fn line_of_sight<T: VisionMap>(map: &T, from: Point, to: Point) -> bool {
+    line_of_sight_blocker(map, from, to).is_ok()
+}
+
+/// Same as [`line_of_sight`], but tells which tile blocked the view.
+/// Returns `Ok(())` if the line is clear, or `Err(position)` with the first opaque tile
+/// encountered on the way from `from` to `to`.
+///
+/// # Panics
+///
+/// Panics if `from` or `to` are out of the map bounds.
+pub fn line_of_sight_blocker<T: VisionMap>(map: &T, from: Point, to: Point) -> Result<(), Point> {
+    assert_in_bounds(map, from.0, from.1);
+    assert_in_bounds(map, to.0, to.1);
+
+    // We skip the first item as it is the origin position.
+    for position in BresenhamLine::new(from, to).skip(1) {
+        if position == to {
+            break;
+        }
+        if !map.is_transparent(position) {
+            return Err(position);
+        }
+    }
+
+    Ok(())
+}
+
+/// Cast a single ray from `from` towards `to`, following a [`BresenhamLine`], and find where
+/// it stops, for instance to know what a bullet hits.
+///
+/// Returns the last transparent tile reached, and the opaque tile that stopped the ray, if
+/// any. Unlike [`line_of_sight_blocker`], `to` itself can stop the ray. The ray also stops at the
+/// edge of the map, without a blocker, so `to` can be out of the map to only give a direction.
+/// `from` is never checked: the last transparent tile is `from` if the ray is stopped right
+/// away.
+///
+/// # Arguments
+///
+/// * `map` - A struct implementing the `VisionMap` trait.
+/// * `from` - The origin of the ray.
+/// * `to` - The end of the ray.
+///
+/// # Panics
+///
+/// Panics if `from` is out of the map bounds.
+///
+/// # Examples
+/// ```
+/// use torchbearer::{fov::cast_ray_until, map::Grid};
+///
+/// let mut map = Grid::new(10, 10);
+/// map.set_transparent((6, 2), false);
+///
+/// assert_eq!(cast_ray_until(&map, (2, 2), (9, 2)), ((5, 2), Some((6, 2))));
+/// assert_eq!(cast_ray_until(&map, (2, 3), (20, 3)), ((9, 3), None));
+/// ```
+pub fn cast_ray_until<T: VisionMap>(map: &T, from: Point, to: Point) -> (Point, Option<Point>) {
+    assert_in_bounds(map, from.0, from.1);
+
+    let mut last_clear = from;
+    // We skip the first item as it is the origin position.
+    for (x, y) in BresenhamLine::new(from, to).skip(1) {
+        if is_out_of_bounds(map, x, y) {
+            break;
+        }
+        if !map.is_transparent((x, y)) {
+            return (last_clear, Some((x, y)));
+        }
+        last_clear = (x, y);
+    }
+
+    (last_clear, None)
+}
+
+fn is_out_of_bounds<M: VisionMap>(map: &M, x: i32, y: i32) -> bool {
+    let (width, height) = map.dimensions();
+    x < 0 || y < 0 || x >= width || y >= height
+}
+
+fn assert_in_bounds<M: VisionMap>(map: &M, x: i32, y: i32) {
+    if let Err(error) = OutOfBounds::check((x, y), map.dimensions()) {
+        panic!("{}", error);
+    }
+}
+
+/// Is the segment between two points clear of opaque tiles, the tiles containing its ends
+/// excepted. Tile `(x, y)` spans from `x - 0.5` to `x + 0.5` horizontally, and likewise
+/// vertically. Going exactly through the corner of a tile doesn't count as crossing it, and
+/// tiles outside of the map are opaque.
+fn is_segment_clear<T: VisionMap>(map: &T, from: (f32, f32), to: (f32, f32)) -> bool {
+    let tile_of = |(x, y): (f32, f32)| ((x + 0.5).floor() as i32, (y + 0.5).floor() as i32);
+    let (mut tile_x, mut tile_y) = tile_of(from);
+    let end = tile_of(to);
+
+    // Amanatides and Woo's traversal: `next_*` is how far along the segment, from 0.0 to 1.0,
+    // the next vertical or horizontal tile border is.
+    let axis = |from: f32, to: f32, tile: i32| {
+        let delta = to - from;
+        if delta > 0. {
+            (1, (tile as f32 + 0.5 - from) / delta, 1. / delta)
+        } else if delta < 0. {
+            (-1, (tile as f32 - 0.5 - from) / delta, -1. / delta)
+        } else {
+            (0, f32::INFINITY, f32::INFINITY)
+        }
+    };
+    let (step_x, mut next_x, delta_x) = axis(from.0, to.0, tile_x);
+    let (step_y, mut next_y, delta_y) = axis(from.1, to.1, tile_y);
+
+    let max_steps = (end.0 - tile_x).abs() + (end.1 - tile_y).abs();
+    for _ in 0..max_steps {
+        if (next_x - next_y).abs() < 1e-6 {
+            tile_x += step_x;
+            tile_y += step_y;
+            next_x += delta_x;
+            next_y += delta_y;
+        } else if next_x < next_y {
+            tile_x += step_x;
+            next_x += delta_x;
+        } else {
+            tile_y += step_y;
+            next_y += delta_y;
+        }
+
+        if (tile_x, tile_y) == end {
+            return true;
+        }
+        if is_out_of_bounds(map, tile_x, tile_y) || !map.is_transparent((tile_x, tile_y)) {
+            return false;
+        }
+    }
+    (tile_x, tile_y) == end
+}
+
+fn cast_ray<T: VisionMap>(
+    map: &T,
+    visibles: &mut [bool],
+    width: i32,
+    height: i32,
+    origin: Point,
+    destination: Point,
     offset: (i32, i32),
 ) {
     // We skip the first item as it is the origin position.
@@ -170,20 +1726,113 @@ fn cast_ray<T: VisionMap>(
         }
         visibles[(off_x + off_y * width) as usize] = true;
 
-        if !map.is_transparent((x, y)) {
-            return;
-        }
+        if !map.is_transparent((x, y)) {
+            return;
+        }
+    }
+}
+
+/// A row of a quadrant scanned by [`field_of_view_shadowcasting`], lit between two slopes.
+/// Slopes are fractions `(numerator, denominator)`, with a positive denominator, to avoid
+/// rounding errors.
+struct ShadowRow {
+    depth: i32,
+    start_slope: (i32, i32),
+    end_slope: (i32, i32),
+}
+
+impl ShadowRow {
+    fn min_column(&self) -> i32 {
+        // Rounds depth * start_slope, ties going up.
+        let (numerator, denominator) = self.start_slope;
+        (2 * self.depth * numerator + denominator).div_euclid(2 * denominator)
+    }
+
+    fn max_column(&self) -> i32 {
+        // Rounds depth * end_slope, ties going down.
+        let (numerator, denominator) = self.end_slope;
+        -(denominator - 2 * self.depth * numerator).div_euclid(2 * denominator)
+    }
+
+    /// Is the center of the tile in the column in the light, which keeps the vision symmetric.
+    fn is_symmetric(&self, column: i32) -> bool {
+        let (start_numerator, start_denominator) = self.start_slope;
+        let (end_numerator, end_denominator) = self.end_slope;
+        column * start_denominator >= self.depth * start_numerator
+            && column * end_denominator <= self.depth * end_numerator
+    }
+
+    /// The slope going through the left edge of the tile.
+    fn slope((depth, column): (i32, i32)) -> (i32, i32) {
+        (2 * column - 1, 2 * depth)
+    }
+}
+
+/// A vision map where some extra positions are opaque.
+struct BlockedVisionMap<'a, T: VisionMap> {
+    map: &'a T,
+    blockers: &'a HashSet<Point>,
+}
+
+impl<'a, T: VisionMap> VisionMap for BlockedVisionMap<'a, T> {
+    fn dimensions(&self) -> (i32, i32) {
+        self.map.dimensions()
+    }
+
+    fn is_transparent(&self, position: Point) -> bool {
+        !self.blockers.contains(&position) && self.map.is_transparent(position)
+    }
+}
+
+/// A square window of `size * size` tiles over a map that wraps around its edges, the tile
+/// `(0, 0)` of the window being the tile `offset` of the map.
+struct WrappingVisionMap<'a, T: VisionMap> {
+    map: &'a T,
+    offset: Point,
+    size: i32,
+}
+
+impl<'a, T: VisionMap> WrappingVisionMap<'a, T> {
+    fn to_map_position(&self, (x, y): Point) -> Point {
+        let (width, height) = self.map.dimensions();
+        (
+            (x + self.offset.0).rem_euclid(width),
+            (y + self.offset.1).rem_euclid(height),
+        )
+    }
+}
+
+impl<'a, T: VisionMap> VisionMap for WrappingVisionMap<'a, T> {
+    fn dimensions(&self) -> (i32, i32) {
+        (self.size, self.size)
+    }
+
+    fn is_transparent(&self, position: Point) -> bool {
+        self.map.is_transparent(self.to_map_position(position))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use rand::{prelude::StdRng, Rng, SeedableRng};
-    use std::fmt::Debug;
+    use std::{collections::HashSet, fmt::Debug};
 
-    use crate::Point;
+    use crate::{
+        bresenham::{Angle, ThickBresenhamCircle},
+        Direction, OutOfBounds, Point,
+    };
 
-    use super::{field_of_view, VisionMap};
+    use super::{
+        cast_ray_until, combine_light_maps, cone_of_view, cover_from, field_of_view,
+        field_of_view_facing, field_of_view_into, field_of_view_iter, field_of_view_many,
+        field_of_view_permissive, field_of_view_ranged, field_of_view_rpas,
+        field_of_view_shadowcasting, field_of_view_shaped, field_of_view_tiered,
+        field_of_view_transparent_only, field_of_view_with_blockers, field_of_view_with_distance,
+        field_of_view_wrapping, is_visible, light_map, line_of_sight, line_of_sight_blocker,
+        lit_wall_faces, raycast_field_of_view, small_field_of_view, tiles_that_can_see,
+        try_field_of_view, Cover, Falloff, FovContext, RadiusShape, VisionMap, RADIUS_1_PERIMETER,
+        RADIUS_2_PERIMETER,
+    };
     const WIDTH: i32 = 45;
     const HEIGHT: i32 = 45;
     const POSITION_X: i32 = 22;
@@ -247,6 +1896,17 @@ mod tests {
         }
     }
 
+    /// A map of `WIDTH` by `HEIGHT`, with walls scattered at random, always the same ones.
+    fn random_walls_map() -> SampleMap {
+        let mut map = SampleMap::new(WIDTH, HEIGHT);
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..RANDOM_WALLS * 10 {
+            let (x, y) = (rng.gen_range(0..WIDTH), rng.gen_range(0..HEIGHT));
+            map.set_transparent(x, y, false);
+        }
+        map
+    }
+
     impl Debug for SampleMap {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             let (width, _height) = self.dimensions();
@@ -335,4 +1995,723 @@ mod tests {
 
         map.calculate_fov(x, y, 2);
     }
+
+    #[test]
+    fn try_fov_out_of_bounds_is_error() {
+        let map = SampleMap::new(WIDTH, HEIGHT);
+
+        assert_eq!(
+            try_field_of_view(&map, (WIDTH, 3), 2),
+            Err(OutOfBounds {
+                position: (WIDTH, 3),
+                dimensions: (WIDTH, HEIGHT),
+            })
+        );
+        assert_eq!(
+            try_field_of_view(&map, (22, 22), 10),
+            Ok(field_of_view(&map, (22, 22), 10))
+        );
+    }
+
+    #[test]
+    fn line_of_sight_clear() {
+        let mut map = SampleMap::new(10, 10);
+        map.set_transparent(5, 5, false);
+
+        assert_eq!(line_of_sight_blocker(&map, (1, 1), (8, 3)), Ok(()));
+        assert!(line_of_sight(&map, (1, 1), (8, 3)));
+        // Walls can be seen.
+        assert!(line_of_sight(&map, (1, 5), (5, 5)));
+    }
+
+    #[test]
+    fn line_of_sight_blocked_by_wall() {
+        let mut map = SampleMap::new(10, 10);
+        map.set_transparent(5, 5, false);
+
+        assert_eq!(line_of_sight_blocker(&map, (1, 5), (8, 5)), Err((5, 5)));
+        assert!(!line_of_sight(&map, (1, 5), (8, 5)));
+    }
+
+    #[test]
+    fn fov_tiered_buckets_by_distance() {
+        let map = SampleMap::new(WIDTH, HEIGHT);
+        let from = (POSITION_X, POSITION_Y);
+
+        let tiered = field_of_view_tiered(&map, from, &[6, 3, 9]);
+        let tier_of = |position: Point| {
+            tiered
+                .iter()
+                .find(|(visible, _)| *visible == position)
+                .map(|&(_, tier)| tier)
+        };
+
+        assert_eq!(tier_of(from), Some(0));
+        assert_eq!(tier_of((POSITION_X + 3, POSITION_Y)), Some(0));
+        assert_eq!(tier_of((POSITION_X + 4, POSITION_Y)), Some(1));
+        assert_eq!(tier_of((POSITION_X, POSITION_Y - 7)), Some(2));
+        assert_eq!(tier_of((POSITION_X + 10, POSITION_Y)), None);
+        for &((x, y), tier) in tiered.iter() {
+            let distance = (((x - from.0).pow(2) + (y - from.1).pow(2)) as f32).sqrt();
+            let (min, max) = [(0., 3.), (3., 6.), (6., 9.)][tier];
+            assert!(distance <= max && (tier == 0 || distance > min));
+        }
+    }
+
+    #[test]
+    fn lit_wall_faces_single_wall() {
+        let mut map = SampleMap::new(10, 10);
+        map.set_transparent(5, 3, false);
+
+        let faces = lit_wall_faces(&map, (2, 3), 8);
+
+        assert_eq!(faces, [((5, 3), Direction::West)]);
+    }
+
+    #[test]
+    fn lit_wall_faces_corner() {
+        let mut map = SampleMap::new(10, 10);
+        map.set_transparent(5, 5, false);
+
+        let mut faces = lit_wall_faces(&map, (2, 2), 8);
+        faces.sort_by_key(|&(_, direction)| direction as u8);
+
+        assert_eq!(
+            faces,
+            [((5, 5), Direction::North), ((5, 5), Direction::West)]
+        );
+    }
+
+    #[test]
+    fn fov_ranged_excludes_near_tiles() {
+        let mut map = SampleMap::new(WIDTH, HEIGHT);
+        map.set_transparent(POSITION_X + 2, POSITION_Y, false);
+
+        let visibles = field_of_view_ranged(&map, (POSITION_X, POSITION_Y), 3, 8);
+
+        assert!(!visibles.contains(&(POSITION_X, POSITION_Y)));
+        assert!(!visibles.contains(&(POSITION_X, POSITION_Y + 2)));
+        assert!(visibles.contains(&(POSITION_X, POSITION_Y + 3)));
+        assert!(visibles.contains(&(POSITION_X - 5, POSITION_Y)));
+        assert!(!visibles.contains(&(POSITION_X, POSITION_Y + 9)));
+        // Shadow of the wall in the near zone.
+        assert!(!visibles.contains(&(POSITION_X + 5, POSITION_Y)));
+    }
+
+    #[test]
+    fn small_radius_perimeters_match_circle() {
+        for (radius, perimeter) in [
+            (1, RADIUS_1_PERIMETER.to_vec()),
+            (2, RADIUS_2_PERIMETER.to_vec()),
+        ] {
+            let mut expected: Vec<_> = ThickBresenhamCircle::new((0, 0), radius).collect();
+            expected.sort();
+            let mut perimeter = perimeter;
+            perimeter.sort();
+
+            assert_eq!(perimeter, expected);
+        }
+    }
+
+    #[test]
+    fn small_radius_fov_matches_raycasting() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for (width, height) in [(12, 9), (1, 6), (2, 2)] {
+            let mut map = SampleMap::new(width, height);
+            for _ in 0..(width * height / 4) {
+                let (x, y) = (rng.gen_range(0..width), rng.gen_range(0..height));
+                map.set_transparent(x, y, false);
+            }
+
+            for radius in 1..=2 {
+                for y in 0..height {
+                    for x in 0..width {
+                        assert_eq!(
+                            small_field_of_view(&map, (x, y), radius),
+                            raycast_field_of_view(&map, (x, y), radius),
+                            "Mismatch at ({}, {}) with radius {}",
+                            x,
+                            y,
+                            radius
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn fov_many_matches_fov() {
+        let map = random_walls_map();
+
+        let sources = [((22, 22), 10), ((0, 0), 5), ((44, 3), 2), ((5, 5), 0)];
+        let visibles = field_of_view_many(&map, &sources);
+
+        assert_eq!(visibles.len(), sources.len());
+        for (visibles, &(from, radius)) in visibles.iter().zip(sources.iter()) {
+            assert_eq!(visibles, &field_of_view(&map, from, radius));
+        }
+    }
+
+    #[test]
+    fn fov_wrapping_matches_fov_away_from_edges() {
+        let map = random_walls_map();
+
+        for (from, radius) in [((22, 22), 10), ((20, 25), 2), ((5, 5), 0)] {
+            assert_eq!(
+                field_of_view_wrapping(&map, from, radius),
+                field_of_view(&map, from, radius)
+            );
+        }
+    }
+
+    #[test]
+    fn fov_wrapping_sees_across_the_seam() {
+        let mut map = SampleMap::new(20, 10);
+        map.set_transparent(17, 5, false);
+
+        let visibles = field_of_view_wrapping(&map, (1, 5), 5);
+
+        // Looking west, through the seam: the wall is visible, but not what's behind it.
+        assert!(visibles.contains(&(19, 5)));
+        assert!(visibles.contains(&(17, 5)));
+        assert!(!visibles.contains(&(16, 5)));
+        assert!(!visibles.contains(&(10, 5)));
+        // Looking north, through the other seam.
+        assert!(field_of_view_wrapping(&map, (1, 2), 5).contains(&(1, 8)));
+    }
+
+    #[test]
+    fn cast_ray_until_stops_on_walls() {
+        let mut map = SampleMap::new(10, 10);
+        map.set_transparent(5, 5, false);
+        map.set_transparent(1, 1, false);
+
+        // Hitting the wall diagonally.
+        assert_eq!(cast_ray_until(&map, (2, 2), (8, 8)), ((4, 4), Some((5, 5))));
+        // The destination itself is the wall.
+        assert_eq!(cast_ray_until(&map, (5, 2), (5, 5)), ((5, 4), Some((5, 5))));
+        // Clear ray, stopping at the destination.
+        assert_eq!(cast_ray_until(&map, (0, 9), (4, 9)), ((4, 9), None));
+        // Blocked right away, the origin is the last clear tile.
+        assert_eq!(cast_ray_until(&map, (2, 2), (0, 0)), ((2, 2), Some((1, 1))));
+        // Leaving the map.
+        assert_eq!(cast_ray_until(&map, (7, 0), (7, -5)), ((7, 0), None));
+    }
+
+    #[test]
+    fn cover_from_wall() {
+        let mut map = SampleMap::new(10, 10);
+        for y in 3..6 {
+            map.set_transparent(5, y, false);
+        }
+
+        assert_eq!(cover_from(&map, (6, 4), (1, 4), 10), Cover::Full);
+        assert_eq!(cover_from(&map, (6, 6), (1, 4), 10), Cover::Partial);
+        assert_eq!(cover_from(&map, (3, 8), (1, 4), 10), Cover::None);
+        assert_eq!(cover_from(&map, (1, 4), (1, 4), 0), Cover::None);
+        // Out of sight.
+        assert_eq!(cover_from(&map, (3, 8), (1, 4), 3), Cover::Full);
+    }
+
+    #[test]
+    fn fov_with_blockers_casts_shadow_like_walls() {
+        let mut map = SampleMap::new(20, 5);
+        for x in 0..20 {
+            map.set_transparent(x, 0, false);
+            map.set_transparent(x, 4, false);
+        }
+
+        let from = (2, 2);
+        let blockers = HashSet::from([(8, 2)]);
+        let with_blockers = field_of_view_with_blockers(&map, from, 15, &blockers);
+
+        map.set_transparent(8, 2, false);
+        let mut with_blockers_sorted = with_blockers.clone();
+        let mut with_wall = field_of_view(&map, from, 15);
+        with_blockers_sorted.sort_unstable();
+        with_wall.sort_unstable();
+
+        assert_eq!(with_blockers_sorted, with_wall);
+        assert!(with_blockers.contains(&(8, 2)));
+        assert!(!with_blockers.contains(&(12, 2)));
+    }
+
+    #[test]
+    fn shadowcasting_is_symmetric() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..3 {
+            let mut map = SampleMap::new(20, 20);
+            for _ in 0..60 {
+                let (x, y) = (rng.gen_range(0..20), rng.gen_range(0..20));
+                map.set_transparent(x, y, false);
+            }
+
+            let floors: Vec<Point> = (0..20)
+                .flat_map(|y| (0..20).map(move |x| (x, y)))
+                .filter(|&position| map.is_transparent(position))
+                .collect();
+            let visions: Vec<Vec<Point>> = floors
+                .iter()
+                .map(|&position| field_of_view_shadowcasting(&map, position, 8))
+                .collect();
+
+            for (a_index, &a) in floors.iter().enumerate() {
+                for (b_index, &b) in floors.iter().enumerate() {
+                    assert_eq!(
+                        visions[a_index].contains(&b),
+                        visions[b_index].contains(&a),
+                        "{:?} and {:?}",
+                        a,
+                        b
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn shadowcasting_walls_cast_shadows() {
+        let mut map = SampleMap::new(11, 11);
+        map.set_transparent(7, 5, false);
+
+        let visibles = field_of_view_shadowcasting(&map, (5, 5), 5);
+
+        assert!(visibles.contains(&(5, 5)));
+        assert!(visibles.contains(&(7, 5)));
+        assert!(!visibles.contains(&(8, 5)));
+        assert!(!visibles.contains(&(10, 5)));
+        assert!(visibles.contains(&(10, 4)) == visibles.contains(&(10, 6)));
+        assert!(visibles.contains(&(5, 0)));
+        assert!(!visibles.contains(&(9, 9)));
+
+        let mut sorted = visibles.clone();
+        sorted.sort_by_key(|&(x, y)| (y, x));
+        sorted.dedup();
+        assert_eq!(sorted, visibles);
+    }
+
+    #[test]
+    fn fov_with_distance() {
+        let map = SampleMap::new(WIDTH, HEIGHT);
+        let from = (POSITION_X, POSITION_Y);
+
+        let visibles = field_of_view_with_distance(&map, from, 5);
+
+        assert_eq!(
+            visibles
+                .iter()
+                .map(|&(position, _)| position)
+                .collect::<Vec<_>>(),
+            field_of_view(&map, from, 5)
+        );
+        assert!(visibles.contains(&(from, 0.)));
+        assert!(visibles.contains(&((POSITION_X + 3, POSITION_Y + 4), 5.)));
+        let diagonal = visibles
+            .iter()
+            .find(|&&(position, _)| position == (POSITION_X + 1, POSITION_Y + 1))
+            .unwrap();
+        assert!((diagonal.1 - std::f32::consts::SQRT_2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn fov_into_matches_fov() {
+        let map = random_walls_map();
+
+        let mut out = vec![true; (WIDTH * HEIGHT) as usize];
+        for (from, radius) in [((22, 22), 10), ((0, 0), 5), ((44, 3), 2), ((5, 5), 0)] {
+            field_of_view_into(&map, from, radius, &mut out);
+            let visibles: Vec<Point> = (0..WIDTH * HEIGHT)
+                .filter(|&index| out[index as usize])
+                .map(|index| (index % WIDTH, index / WIDTH))
+                .collect();
+
+            assert_eq!(visibles, field_of_view(&map, from, radius));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "out should contain 2025 elements for a map of (45, 45), got 10.")]
+    fn fov_into_wrong_size_panics() {
+        let map = SampleMap::new(WIDTH, HEIGHT);
+        field_of_view_into(&map, (1, 1), 3, &mut [false; 10]);
+    }
+
+    #[test]
+    fn fov_iter_matches_fov() {
+        let map = random_walls_map();
+
+        for (from, radius) in [((22, 22), 10), ((0, 0), 5), ((44, 3), 2), ((5, 5), 0)] {
+            assert_eq!(
+                field_of_view_iter(&map, from, radius).collect::<Vec<_>>(),
+                field_of_view(&map, from, radius)
+            );
+        }
+    }
+
+    #[test]
+    fn fov_shaped_square_includes_corners() {
+        let map = SampleMap::new(WIDTH, HEIGHT);
+        let from = (POSITION_X, POSITION_Y);
+        let corners =
+            [(-2, -2), (2, -2), (-2, 2), (2, 2)].map(|(dx, dy)| (POSITION_X + dx, POSITION_Y + dy));
+
+        let circle = field_of_view_shaped(&map, from, 2, RadiusShape::Circle);
+        let square = field_of_view_shaped(&map, from, 2, RadiusShape::Square);
+        let diamond = field_of_view_shaped(&map, from, 2, RadiusShape::Diamond);
+
+        assert_eq!(circle, field_of_view(&map, from, 2));
+        assert_eq!(square.len(), 25);
+        assert_eq!(diamond.len(), 13);
+        for corner in corners {
+            assert!(square.contains(&corner));
+            assert!(!circle.contains(&corner));
+            assert!(!diamond.contains(&corner));
+        }
+    }
+
+    #[test]
+    fn fov_shaped_square_is_blocked_by_walls() {
+        let mut map = SampleMap::new(WIDTH, HEIGHT);
+        map.set_transparent(POSITION_X + 1, POSITION_Y, false);
+
+        let square = field_of_view_shaped(&map, (POSITION_X, POSITION_Y), 5, RadiusShape::Square);
+
+        assert!(square.contains(&(POSITION_X + 1, POSITION_Y)));
+        assert!(!square.contains(&(POSITION_X + 4, POSITION_Y)));
+        assert!(square.contains(&(POSITION_X + 5, POSITION_Y + 5)));
+    }
+
+    #[test]
+    fn light_map_fades_with_distance() {
+        let map = SampleMap::new(WIDTH, HEIGHT);
+        let from = (POSITION_X, POSITION_Y);
+
+        for falloff in [Falloff::Linear, Falloff::Quadratic, Falloff::InverseSquare] {
+            let lights = light_map(&map, from, 6, falloff);
+            let intensity_at = |dx: i32| {
+                lights
+                    .iter()
+                    .find(|&&(position, _)| position == (POSITION_X + dx, POSITION_Y))
+                    .map(|&(_, intensity)| intensity)
+                    .unwrap()
+            };
+
+            assert_eq!(lights.len(), field_of_view(&map, from, 6).len());
+            assert_eq!(intensity_at(0), 1.);
+            assert!(intensity_at(6).abs() < 1e-6);
+            for dx in 0..6 {
+                assert!(intensity_at(dx) > intensity_at(dx + 1));
+            }
+            assert!(lights
+                .iter()
+                .all(|&(_, intensity)| (0. ..=1.).contains(&intensity)));
+        }
+    }
+
+    #[test]
+    fn light_map_falloff_curves() {
+        let map = SampleMap::new(WIDTH, HEIGHT);
+        let intensity_at_half = |falloff| {
+            light_map(&map, (POSITION_X, POSITION_Y), 4, falloff)
+                .into_iter()
+                .find(|&(position, _)| position == (POSITION_X + 2, POSITION_Y))
+                .map(|(_, intensity)| intensity)
+                .unwrap()
+        };
+
+        assert_eq!(intensity_at_half(Falloff::Linear), 0.5);
+        assert_eq!(intensity_at_half(Falloff::Quadratic), 0.25);
+        assert!(intensity_at_half(Falloff::InverseSquare) < 0.25);
+    }
+
+    #[test]
+    fn combine_light_maps_adds_up_intensities() {
+        let map = SampleMap::new(WIDTH, HEIGHT);
+        let first = light_map(&map, (20, 22), 6, Falloff::Linear);
+        let second = light_map(&map, (24, 22), 6, Falloff::Linear);
+
+        let single = combine_light_maps(std::slice::from_ref(&first), WIDTH, HEIGHT);
+        let combined = combine_light_maps(&[first, second], WIDTH, HEIGHT);
+        let index = |x: i32, y: i32| (x + y * WIDTH) as usize;
+
+        assert_eq!(combined.len(), (WIDTH * HEIGHT) as usize);
+        assert!(combined[index(22, 22)] > single[index(22, 22)]);
+        assert_eq!(combined[index(20, 22)], 1.);
+        assert_eq!(combined[index(24, 22)], 1.);
+        assert_eq!(combined[index(0, 0)], 0.);
+        assert!(combined
+            .iter()
+            .all(|&intensity| (0. ..=1.).contains(&intensity)));
+    }
+
+    #[test]
+    #[should_panic(expected = "(x, y) should be between (0,0) and (10, 10), got (10, 3).")]
+    fn combine_light_maps_out_of_bounds_panics() {
+        combine_light_maps(&[vec![((10, 3), 0.5)]], 10, 10);
+    }
+
+    #[test]
+    fn cone_of_view_fractional_radius() {
+        let map = SampleMap::new(WIDTH, HEIGHT);
+        let from = (POSITION_X, POSITION_Y);
+        let visible = cone_of_view(&map, from, 3.5, Angle::Degrees(0.), Angle::Degrees(90.));
+
+        assert!(visible.contains(&from));
+        // At a distance of √10 ≈ 3.16.
+        assert!(visible.contains(&(POSITION_X + 3, POSITION_Y + 1)));
+        // At a distance of √13 ≈ 3.61, just past the radius.
+        assert!(!visible.contains(&(POSITION_X + 3, POSITION_Y + 2)));
+        assert!(field_of_view(&map, from, 4).contains(&(POSITION_X + 3, POSITION_Y + 2)));
+        // Out of the cone.
+        assert!(!visible.contains(&(POSITION_X - 1, POSITION_Y)));
+        assert!(!visible.contains(&(POSITION_X + 1, POSITION_Y - 1)));
+    }
+
+    #[test]
+    fn cone_of_view_wraps_around_east() {
+        let map = SampleMap::new(WIDTH, HEIGHT);
+        let from = (POSITION_X, POSITION_Y);
+        let visible = cone_of_view(
+            &map,
+            from,
+            5.,
+            Angle::Degrees(270.),
+            Angle::Radians(std::f32::consts::FRAC_PI_2),
+        );
+
+        assert!(visible.contains(&(POSITION_X, POSITION_Y - 5)));
+        assert!(visible.contains(&(POSITION_X + 5, POSITION_Y)));
+        assert!(visible.contains(&(POSITION_X, POSITION_Y + 5)));
+        assert!(!visible.contains(&(POSITION_X - 1, POSITION_Y)));
+        assert!(!visible.contains(&(POSITION_X - 3, POSITION_Y + 2)));
+    }
+
+    #[test]
+    fn cone_of_view_full_turn_is_field_of_view() {
+        let map = SampleMap::new(WIDTH, HEIGHT);
+        let from = (POSITION_X, POSITION_Y);
+        let expected = field_of_view(&map, from, 5);
+
+        assert_eq!(
+            cone_of_view(&map, from, 5., Angle::Degrees(0.), Angle::Degrees(360.)),
+            expected
+        );
+        assert_eq!(
+            cone_of_view(&map, from, 5., Angle::Degrees(-180.), Angle::Degrees(180.)),
+            expected
+        );
+    }
+
+    #[test]
+    fn fov_rpas_no_walls_sees_the_whole_disc() {
+        let map = SampleMap::new(WIDTH, HEIGHT);
+        let visible = field_of_view_rpas(&map, (POSITION_X, POSITION_Y), 6);
+
+        let mut disc = vec![];
+        for y in POSITION_Y - 6..=POSITION_Y + 6 {
+            for x in POSITION_X - 6..=POSITION_X + 6 {
+                let (dx, dy) = (x - POSITION_X, y - POSITION_Y);
+                if dx * dx + dy * dy <= 36 {
+                    disc.push((x, y));
+                }
+            }
+        }
+
+        assert_eq!(visible, disc);
+    }
+
+    #[test]
+    fn fov_rpas_pillar_casts_a_clean_shadow() {
+        // . . . . x x
+        // @ . # x x x x
+        // . . . . x x
+        let mut map = SampleMap::new(15, 15);
+        map.set_transparent(9, 7, false);
+        let without_pillar = field_of_view_rpas(&SampleMap::new(15, 15), (7, 7), 6);
+
+        let visible = field_of_view_rpas(&map, (7, 7), 6);
+        let hidden: Vec<_> = without_pillar
+            .into_iter()
+            .filter(|position| !visible.contains(position))
+            .collect();
+
+        assert!(visible.contains(&(9, 7)));
+        assert_eq!(
+            hidden,
+            vec![
+                (11, 6),
+                (12, 6),
+                (10, 7),
+                (11, 7),
+                (12, 7),
+                (13, 7),
+                (11, 8),
+                (12, 8)
+            ]
+        );
+    }
+
+    #[test]
+    fn fov_permissive_peeks_around_corners() {
+        // . . . . . . . . # . . .
+        // . . . . . . . @ # . . .
+        // . . . . . . . . . . . .
+        // # # # # # # # # . . . .
+        let mut map = SampleMap::new(15, 15);
+        for y in 0..=6 {
+            map.set_transparent(8, y, false);
+        }
+        for x in 0..=7 {
+            map.set_transparent(x, 8, false);
+        }
+        let from = (7, 6);
+
+        let raycast = field_of_view(&map, from, 6);
+        let strict = field_of_view_permissive(&map, from, 6, 0);
+        let permissive = field_of_view_permissive(&map, from, 6, 8);
+
+        for peeked in [(10, 7), (11, 7), (11, 8)] {
+            assert!(!raycast.contains(&peeked));
+            assert!(!strict.contains(&peeked));
+            assert!(permissive.contains(&peeked));
+        }
+        assert!(strict.iter().all(|position| permissive.contains(position)));
+    }
+
+    #[test]
+    #[should_panic(expected = "permissiveness should be between 0 and 8, got 9.")]
+    fn fov_permissive_too_permissive_panics() {
+        let map = SampleMap::new(WIDTH, HEIGHT);
+        field_of_view_permissive(&map, (POSITION_X, POSITION_Y), RADIUS, 9);
+    }
+
+    #[test]
+    fn fov_transparent_only_leaves_walls_out() {
+        // A 7x7 room, bounded by walls, inside a bigger map.
+        let mut map = SampleMap::new(WIDTH, HEIGHT);
+        for offset in -4..=4 {
+            map.set_transparent(POSITION_X + offset, POSITION_Y - 4, false);
+            map.set_transparent(POSITION_X + offset, POSITION_Y + 4, false);
+            map.set_transparent(POSITION_X - 4, POSITION_Y + offset, false);
+            map.set_transparent(POSITION_X + 4, POSITION_Y + offset, false);
+        }
+        let from = (POSITION_X, POSITION_Y);
+
+        let with_walls = field_of_view(&map, from, RADIUS);
+        let without_walls = field_of_view_transparent_only(&map, from, RADIUS);
+
+        assert_eq!(with_walls.len(), 81);
+        assert_eq!(without_walls.len(), 49);
+        assert!(without_walls
+            .iter()
+            .all(|&position| map.is_transparent(position)));
+    }
+
+    #[test]
+    fn fov_facing_east() {
+        let map = SampleMap::new(WIDTH, HEIGHT);
+        let from = (POSITION_X, POSITION_Y);
+        let visible = field_of_view_facing(&map, from, 5, Angle::Degrees(0.), Angle::Degrees(90.));
+
+        assert!(visible.contains(&from));
+        assert!(visible.contains(&(POSITION_X + 5, POSITION_Y)));
+        assert!(visible.contains(&(POSITION_X + 3, POSITION_Y - 3)));
+        assert!(visible.contains(&(POSITION_X + 3, POSITION_Y + 3)));
+        assert!(!visible.contains(&(POSITION_X + 2, POSITION_Y - 3)));
+        assert!(!visible.contains(&(POSITION_X, POSITION_Y + 1)));
+        assert!(!visible.contains(&(POSITION_X - 1, POSITION_Y)));
+    }
+
+    #[test]
+    fn fov_facing_wraps_around() {
+        let map = SampleMap::new(WIDTH, HEIGHT);
+        let from = (POSITION_X, POSITION_Y);
+
+        let facing_east =
+            field_of_view_facing(&map, from, 5, Angle::Degrees(0.), Angle::Degrees(90.));
+        let facing_full_turn =
+            field_of_view_facing(&map, from, 5, Angle::Degrees(360.), Angle::Degrees(90.));
+        let all_around =
+            field_of_view_facing(&map, from, 5, Angle::Degrees(42.), Angle::Degrees(360.));
+
+        assert_eq!(facing_east, facing_full_turn);
+        assert_eq!(all_around, field_of_view(&map, from, 5));
+    }
+
+    #[test]
+    fn tiles_that_can_see_follow_lines_from_the_watchers() {
+        let mut map = SampleMap::new(10, 10);
+        map.set_transparent(6, 6, false);
+        let target = (5, 5);
+
+        let watchers = tiles_that_can_see(&map, target, 3);
+
+        // The line from the target goes through (6, 5), the one back through the wall.
+        assert!(line_of_sight(&map, target, (7, 6)));
+        assert!(!watchers.contains(&(7, 6)));
+        assert!(!watchers.contains(&(6, 6)));
+        assert!(!watchers.contains(&(7, 7)));
+        assert!(watchers.contains(&target));
+        assert!(watchers.contains(&(8, 5)));
+        assert!(!watchers.contains(&(8, 7)));
+    }
+
+    #[test]
+    fn is_visible_matches_fov() {
+        let map = random_walls_map();
+
+        for (from, radius) in [
+            ((22, 22), 10),
+            ((0, 0), 5),
+            ((44, 3), 2),
+            ((5, 5), 1),
+            ((5, 5), 0),
+        ] {
+            let visibles = field_of_view(&map, from, radius);
+            for y in 0..HEIGHT {
+                for x in 0..WIDTH {
+                    assert_eq!(
+                        is_visible(&map, from, (x, y), radius),
+                        visibles.contains(&(x, y)),
+                        "from {:?} to {:?} with a radius of {}",
+                        from,
+                        (x, y),
+                        radius
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn fov_context_matches_fov() {
+        let map = random_walls_map();
+        let mut context = FovContext::new();
+
+        for (from, radius) in [((22, 22), 10), ((0, 0), 5), ((44, 3), 2), ((5, 5), 0)] {
+            assert_eq!(
+                context.compute(&map, from, radius),
+                field_of_view(&map, from, radius)
+            );
+        }
+    }
+
+    #[test]
+    fn fov_context_does_not_reallocate() {
+        let map = SampleMap::new(WIDTH, HEIGHT);
+        let mut context = FovContext::new();
+        context.compute(&map, (POSITION_X, POSITION_Y), RADIUS);
+        let visibles = context.visibles.as_ptr();
+        let positions = context.positions.as_ptr();
+
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..1000 {
+            let from = (rng.gen_range(0..WIDTH), rng.gen_range(0..HEIGHT));
+            context.compute(&map, from, rng.gen_range(0..=RADIUS));
+
+            assert_eq!(context.visibles.as_ptr(), visibles);
+            assert_eq!(context.positions.as_ptr(), positions);
+        }
+    }
 }
@@ -0,0 +1,410 @@
+//! Pathfinding and field of view on hexagonal grids, using axial coordinates.
+//!
+//! A hex is identified by its axial coordinates `(q, r)`, stored in a [`Point`]. A map of
+//! dimensions `(width, height)` contains the hexes with `0 <= q < width` and `0 <= r < height`,
+//! which draws a rhombus. See [redblobgames' guide](https://www.redblobgames.com/grids/hexagons/)
+//! for more details about axial coordinates.
+
+use crate::{
+    path::{Graph, NodeId},
+    OutOfBounds, Point,
+};
+
+/// The offsets to the six neighboors of a hex, in axial coordinates.
+pub const HEX_DIRECTIONS: [Point; 6] = [(1, 0), (1, -1), (0, -1), (-1, 0), (-1, 1), (0, 1)];
+
+/// Implement the HexMap trait to use the pathfinding and field of view functions on a
+/// hexagonal grid.
+pub trait HexMap {
+    /// Dimension of your map, in axial coordinates: `q` goes from `0` to `width - 1`, and `r`
+    /// from `0` to `height - 1`.
+    fn dimensions(&self) -> (i32, i32);
+    /// Wether it is possible or not to walk through the hex at position `(q, r)`.
+    fn is_walkable(&self, position: Point) -> bool;
+    /// How much it costs to walk into the hex at position `(q, r)`, `1.0` by default.
+    /// Keep it at `1.0` or more, see [`PathMap::cost`](crate::path::PathMap::cost).
+    fn cost(&self, _position: Point) -> f32 {
+        1.
+    }
+    /// Wether it is possible or not to see through the hex at position `(q, r)`. By default,
+    /// the walkable hexes are the transparent ones.
+    fn is_transparent(&self, position: Point) -> bool {
+        self.is_walkable(position)
+    }
+}
+
+/// The number of steps between two hexes, in axial coordinates.
+///
+/// # Examples
+/// ```
+/// use torchbearer::hex::hex_distance;
+///
+/// assert_eq!(hex_distance((0, 0), (3, -1)), 3);
+/// assert_eq!(hex_distance((0, 0), (2, 2)), 4);
+/// ```
+pub fn hex_distance((qa, ra): Point, (qb, rb): Point) -> i32 {
+    let (dq, dr) = (qa - qb, ra - rb);
+    (dq.abs() + dr.abs() + (dq + dr).abs()) / 2
+}
+
+/// The hexes on the straight line between `from` and `to`, both included, in order.
+/// There are `hex_distance(from, to) + 1` of them, each one a neighboor of the previous one.
+///
+/// When the line goes exactly between two hexes, it picks the same side every time.
+///
+/// # Examples
+/// ```
+/// use torchbearer::hex::hex_line;
+///
+/// assert_eq!(hex_line((0, 0), (3, 0)), [(0, 0), (1, 0), (2, 0), (3, 0)]);
+/// assert_eq!(hex_line((0, 0), (2, -1)).len(), 3);
+/// ```
+pub fn hex_line(from: Point, to: Point) -> Vec<Point> {
+    let distance = hex_distance(from, to);
+    if distance == 0 {
+        return vec![from];
+    }
+
+    let ((qa, ra), (qb, rb)) = (from, to);
+    (0..=distance)
+        .map(|step| {
+            let t = step as f32 / distance as f32;
+            // The nudge breaks the ties when the line goes along the edge between two hexes.
+            hex_round(
+                qa as f32 + (qb - qa) as f32 * t + 1e-4,
+                ra as f32 + (rb - ra) as f32 * t + 1e-4,
+            )
+        })
+        .collect()
+}
+
+/// The hex containing the fractional axial position `(q, r)`.
+fn hex_round(q: f32, r: f32) -> Point {
+    let s = -q - r;
+    let (mut rounded_q, mut rounded_r, rounded_s) = (q.round(), r.round(), s.round());
+    let (diff_q, diff_r, diff_s) = (
+        (rounded_q - q).abs(),
+        (rounded_r - r).abs(),
+        (rounded_s - s).abs(),
+    );
+
+    if diff_q > diff_r && diff_q > diff_s {
+        rounded_q = -rounded_r - rounded_s;
+    } else if diff_r > diff_s {
+        rounded_r = -rounded_q - rounded_s;
+    }
+
+    (rounded_q as i32, rounded_r as i32)
+}
+
+/// The hexes exactly `radius` steps away from `center`, going around it.
+fn hex_ring(center: Point, radius: i32) -> Vec<Point> {
+    let (start_q, start_r) = HEX_DIRECTIONS[4];
+    let mut current = (center.0 + start_q * radius, center.1 + start_r * radius);
+    let mut ring = Vec::with_capacity(6 * radius as usize);
+    for (dq, dr) in HEX_DIRECTIONS {
+        for _ in 0..radius {
+            ring.push(current);
+            current = (current.0 + dq, current.1 + dr);
+        }
+    }
+    ring
+}
+
+/// Compute the field of view on a hexagonal grid. Returns a vector containing all hexes
+/// visible from the starting position, including the starting position, ordered by index
+/// `q + r * width`.
+///
+/// Casts a [`hex_line`] towards each hex of the ring at `radius` hexes: a hex is visible if the
+/// line reaches it before going through an opaque hex. Opaque hexes are visible themselves.
+///
+/// # Arguments
+///
+/// * `map` - A struct implementing the `HexMap` trait.
+/// * `from` - The origin/center of the field of vision.
+/// * `radius` - How far the vision should go, in hexes. If 0 or less, you only see yourself.
+///
+/// # Panics
+///
+/// Panics if `from` is out of the map bounds.
+///
+/// # Examples
+/// ```
+/// use torchbearer::{
+///     hex::{field_of_view_hex, HexMap},
+///     Point,
+/// };
+///
+/// struct Cave;
+///
+/// impl HexMap for Cave {
+///     fn dimensions(&self) -> (i32, i32) {
+///         (10, 10)
+///     }
+///
+///     fn is_walkable(&self, position: Point) -> bool {
+///         position != (6, 5)
+///     }
+/// }
+///
+/// let visibles = field_of_view_hex(&Cave, (4, 5), 4);
+///
+/// assert!(visibles.contains(&(6, 5)));
+/// assert!(!visibles.contains(&(7, 5)));
+/// assert!(visibles.contains(&(4, 1)));
+/// ```
+pub fn field_of_view_hex<T: HexMap>(map: &T, from: Point, radius: i32) -> Vec<Point> {
+    let (width, height) = map.dimensions();
+    if let Err(error) = OutOfBounds::check(from, (width, height)) {
+        panic!("{}", error);
+    }
+    if radius < 1 {
+        return vec![from];
+    }
+
+    let mut visibles = vec![false; (width * height) as usize];
+    visibles[(from.0 + from.1 * width) as usize] = true;
+    for destination in hex_ring(from, radius) {
+        for (q, r) in hex_line(from, destination).into_iter().skip(1) {
+            if q < 0 || r < 0 || q >= width || r >= height {
+                break;
+            }
+            visibles[(q + r * width) as usize] = true;
+            if !map.is_transparent((q, r)) {
+                break;
+            }
+        }
+    }
+
+    (0..width * height)
+        .filter(|&index| visibles[index as usize])
+        .map(|index| (index % width, index / width))
+        .collect()
+}
+
+/// A wrapper around a HexMap, representing the graph of a hexagonal grid, where each hex has
+/// six neighboors.
+///
+/// # Examples
+/// ```
+/// use torchbearer::{
+///     hex::{HexGridGraph, HexMap},
+///     path::astar_path,
+///     Point,
+/// };
+///
+/// struct Island;
+///
+/// impl HexMap for Island {
+///     fn dimensions(&self) -> (i32, i32) {
+///         (8, 8)
+///     }
+///
+///     fn is_walkable(&self, _position: Point) -> bool {
+///         true
+///     }
+/// }
+///
+/// let graph = HexGridGraph::new(&Island);
+/// let from = graph.point_to_index((0, 4));
+/// let to = graph.point_to_index((4, 0));
+///
+/// // Going up-right is a single step on a hex grid.
+/// let path = astar_path(&graph, from, to).unwrap();
+/// assert_eq!(path.len(), 5);
+/// ```
+pub struct HexGridGraph<'a, T: HexMap + ?Sized> {
+    map: &'a T,
+    width: i32,
+    height: i32,
+}
+
+impl<'a, T: HexMap + ?Sized> HexGridGraph<'a, T> {
+    pub fn new(map: &'a T) -> Self {
+        let (width, height) = map.dimensions();
+        HexGridGraph { map, width, height }
+    }
+
+    /// The `NodeId` of the hex at position `(q, r)`: `q + r * width`.
+    pub fn point_to_index(&self, (q, r): Point) -> NodeId {
+        (q + r * self.width) as usize
+    }
+
+    /// The position `(q, r)` of the hex with the given `NodeId`.
+    pub fn index_to_point(&self, index: NodeId) -> Point {
+        (index as i32 % self.width, index as i32 / self.width)
+    }
+}
+
+impl<'a, T: HexMap + ?Sized> Graph for HexGridGraph<'a, T> {
+    fn node_count(&self) -> usize {
+        (self.width * self.height) as usize
+    }
+
+    fn cost_between(&self, _a: NodeId, b: NodeId) -> f32 {
+        self.map.cost(self.index_to_point(b))
+    }
+
+    fn heuristic(&self, a: NodeId, b: NodeId) -> f32 {
+        hex_distance(self.index_to_point(a), self.index_to_point(b)) as f32
+    }
+
+    fn neighboors(&self, a: NodeId, into: &mut Vec<NodeId>) {
+        let (q, r) = self.index_to_point(a);
+
+        for (dq, dr) in HEX_DIRECTIONS {
+            let (nq, nr) = (q + dq, r + dr);
+            if nq < 0 || nr < 0 || nq >= self.width || nr >= self.height {
+                continue;
+            }
+            if self.map.is_walkable((nq, nr)) {
+                into.push(self.point_to_index((nq, nr)));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{path::astar_path, Point};
+
+    use super::{field_of_view_hex, hex_distance, hex_line, hex_ring, HexGridGraph, HexMap};
+
+    struct SampleHexMap {
+        width: i32,
+        height: i32,
+        walkable: Vec<bool>,
+    }
+
+    impl SampleHexMap {
+        fn new(width: i32, height: i32) -> Self {
+            SampleHexMap {
+                width,
+                height,
+                walkable: vec![true; (width * height) as usize],
+            }
+        }
+
+        fn build_wall(&mut self, (q, r): Point) {
+            self.walkable[(q + r * self.width) as usize] = false;
+        }
+    }
+
+    impl HexMap for SampleHexMap {
+        fn dimensions(&self) -> (i32, i32) {
+            (self.width, self.height)
+        }
+
+        fn is_walkable(&self, (q, r): Point) -> bool {
+            self.walkable[(q + r * self.width) as usize]
+        }
+    }
+
+    #[test]
+    fn hex_distance_is_symmetric() {
+        assert_eq!(hex_distance((2, 3), (2, 3)), 0);
+        assert_eq!(hex_distance((2, 3), (5, 1)), 3);
+        assert_eq!(hex_distance((5, 1), (2, 3)), 3);
+        assert_eq!(hex_distance((0, 0), (-2, -2)), 4);
+    }
+
+    #[test]
+    fn hex_path_goes_around_wall() {
+        let mut map = SampleHexMap::new(7, 7);
+        // A wall along q = 3, with a gap at r = 6.
+        for r in 0..6 {
+            map.build_wall((3, r));
+        }
+        let graph = HexGridGraph::new(&map);
+
+        let path: Vec<Point> = astar_path(
+            &graph,
+            graph.point_to_index((0, 3)),
+            graph.point_to_index((6, 3)),
+        )
+        .unwrap()
+        .into_iter()
+        .map(|index| graph.index_to_point(index))
+        .collect();
+
+        // 6 steps to the gap at (3, 6), then 3 steps to the destination.
+        assert_eq!(path.len(), 10);
+        assert_eq!(path.first(), Some(&(0, 3)));
+        assert_eq!(path.last(), Some(&(6, 3)));
+        assert!(path.contains(&(3, 6)));
+        for step in path.windows(2) {
+            assert_eq!(hex_distance(step[0], step[1]), 1);
+            assert!(map.is_walkable(step[1]));
+        }
+    }
+
+    #[test]
+    fn hex_path_blocked() {
+        let mut map = SampleHexMap::new(7, 7);
+        for r in 0..7 {
+            map.build_wall((3, r));
+        }
+        let graph = HexGridGraph::new(&map);
+
+        assert_eq!(
+            astar_path(
+                &graph,
+                graph.point_to_index((0, 3)),
+                graph.point_to_index((6, 3))
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn hex_line_steps_to_neighboors() {
+        for destination in hex_ring((0, 0), 7) {
+            let line = hex_line((0, 0), destination);
+
+            assert_eq!(line.len(), 8);
+            assert_eq!(line.last(), Some(&destination));
+            for step in line.windows(2) {
+                assert_eq!(hex_distance(step[0], step[1]), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn hex_fov_sees_every_hex_in_range() {
+        let map = SampleHexMap::new(15, 15);
+
+        let visibles = field_of_view_hex(&map, (7, 7), 4);
+
+        let expected: Vec<Point> = (0..15)
+            .flat_map(|r| (0..15).map(move |q| (q, r)))
+            .filter(|&hex| hex_distance((7, 7), hex) <= 4)
+            .collect();
+        assert_eq!(visibles, expected);
+    }
+
+    #[test]
+    fn hex_fov_blocker_casts_shadow_wedge() {
+        let mut map = SampleHexMap::new(15, 15);
+        map.build_wall((9, 7));
+
+        let visibles = field_of_view_hex(&map, (7, 7), 5);
+        let hidden: Vec<Point> = (0..15)
+            .flat_map(|r| (0..15).map(move |q| (q, r)))
+            .filter(|&hex| hex_distance((7, 7), hex) <= 5 && !visibles.contains(&hex))
+            .collect();
+        // The hexes right behind the blocker, widening symmetrically with the distance.
+        assert_eq!(
+            hidden,
+            [
+                (11, 6),
+                (12, 6),
+                (10, 7),
+                (11, 7),
+                (12, 7),
+                (10, 8),
+                (11, 8)
+            ]
+        );
+        assert!(visibles.contains(&(9, 7)));
+    }
+}
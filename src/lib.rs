@@ -1,8 +1,308 @@
 #![doc = include_str!("../README.md")]
 
 pub mod bresenham;
+#[cfg(feature = "coord")]
+pub mod coord;
 pub mod fov;
+pub mod hex;
+pub mod map;
 pub mod path;
 
+use std::fmt;
+
 /// A convenience type alias for a position tuple.
 pub type Point = (i32, i32);
+
+/// The error returned by the `try_` functions, like
+/// [`try_field_of_view`](fov::try_field_of_view), when a position is out of the map bounds.
+///
+/// # Examples
+/// ```
+/// use torchbearer::{fov::try_field_of_view, map::Grid, OutOfBounds};
+///
+/// let map = Grid::new(10, 10);
+///
+/// assert_eq!(
+///     try_field_of_view(&map, (12, 3), 5),
+///     Err(OutOfBounds {
+///         position: (12, 3),
+///         dimensions: (10, 10),
+///     })
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutOfBounds {
+    /// The offending position.
+    pub position: Point,
+    /// The dimensions of the map.
+    pub dimensions: (i32, i32),
+}
+
+impl OutOfBounds {
+    /// Returns an error if `position` is out of a map of size `dimensions`.
+    pub(crate) fn check(position: Point, dimensions: (i32, i32)) -> Result<(), OutOfBounds> {
+        let ((x, y), (width, height)) = (position, dimensions);
+        if x < 0 || y < 0 || x >= width || y >= height {
+            Err(OutOfBounds {
+                position,
+                dimensions,
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl fmt::Display for OutOfBounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ((x, y), (width, height)) = (self.position, self.dimensions);
+        write!(
+            f,
+            "(x, y) should be between (0,0) and ({}, {}), got ({}, {}).",
+            width, height, x, y
+        )
+    }
+}
+
+impl std::error::Error for OutOfBounds {}
+
+/// The eight directions of a grid. North is up, towards negative `y`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    /// The offset to add to a position to move one tile in this direction.
+    pub fn offset(&self) -> Point {
+        match self {
+            Direction::North => (0, -1),
+            Direction::NorthEast => (1, -1),
+            Direction::East => (1, 0),
+            Direction::SouthEast => (1, 1),
+            Direction::South => (0, 1),
+            Direction::SouthWest => (-1, 1),
+            Direction::West => (-1, 0),
+            Direction::NorthWest => (-1, -1),
+        }
+    }
+
+    /// The direction matching an offset of one tile, or `None` if `offset` is `(0, 0)` or
+    /// more than one tile away.
+    ///
+    /// # Examples
+    /// ```
+    /// use torchbearer::Direction;
+    ///
+    /// assert_eq!(Direction::from_offset((-1, 1)), Some(Direction::SouthWest));
+    /// assert_eq!(Direction::from_offset((2, 0)), None);
+    /// ```
+    pub fn from_offset(offset: Point) -> Option<Direction> {
+        Direction::all().find(|direction| direction.offset() == offset)
+    }
+
+    /// The position one tile away from `position`, in this direction.
+    ///
+    /// # Examples
+    /// ```
+    /// use torchbearer::Direction;
+    ///
+    /// assert_eq!(Direction::NorthEast.apply((3, 3)), (4, 2));
+    /// ```
+    pub fn apply(&self, (x, y): Point) -> Point {
+        let (dx, dy) = self.offset();
+        (x + dx, y + dy)
+    }
+
+    /// The four cardinal directions, clockwise, starting from north.
+    ///
+    /// # Examples
+    /// ```
+    /// use torchbearer::Direction;
+    ///
+    /// let neighboors: Vec<_> = Direction::cardinals()
+    ///     .map(|direction| direction.apply((3, 3)))
+    ///     .collect();
+    /// assert_eq!(neighboors, [(3, 2), (4, 3), (3, 4), (2, 3)]);
+    /// ```
+    pub fn cardinals() -> impl Iterator<Item = Direction> {
+        [
+            Direction::North,
+            Direction::East,
+            Direction::South,
+            Direction::West,
+        ]
+        .into_iter()
+    }
+
+    /// The eight directions, clockwise, starting from north.
+    pub fn all() -> impl Iterator<Item = Direction> {
+        [
+            Direction::North,
+            Direction::NorthEast,
+            Direction::East,
+            Direction::SouthEast,
+            Direction::South,
+            Direction::SouthWest,
+            Direction::West,
+            Direction::NorthWest,
+        ]
+        .into_iter()
+    }
+}
+
+/// Rotate a point of a grid of size `(width, height)` by a quarter turn, clockwise.
+///
+/// The rotated grid is of size `(height, width)`: to rotate several times, swap the dimensions
+/// between each call.
+///
+/// # Examples
+/// ```
+/// use torchbearer::rotate_point_cw;
+///
+/// // The top left corner of a 4x3 grid becomes the top right corner of a 3x4 grid.
+/// assert_eq!(rotate_point_cw((0, 0), (4, 3)), (2, 0));
+/// ```
+pub fn rotate_point_cw((x, y): Point, (_width, height): (i32, i32)) -> Point {
+    (height - 1 - y, x)
+}
+
+/// Mirror a point horizontally, across the vertical axis of a grid of width `width`.
+pub fn mirror_x((x, y): Point, width: i32) -> Point {
+    (width - 1 - x, y)
+}
+
+/// Mirror a point vertically, across the horizontal axis of a grid of height `height`.
+pub fn mirror_y((x, y): Point, height: i32) -> Point {
+    (x, height - 1 - y)
+}
+
+/// The bounding box of a set of tiles, as its `(min, max)` corners, both included.
+/// Returns `None` if `tiles` is empty.
+///
+/// # Examples
+/// ```
+/// use torchbearer::tile_set_bounds;
+///
+/// assert_eq!(tile_set_bounds(&[(2, 5), (4, 1), (3, 3)]), Some(((2, 1), (4, 5))));
+/// ```
+pub fn tile_set_bounds(tiles: &[Point]) -> Option<(Point, Point)> {
+    let (&first, rest) = tiles.split_first()?;
+    Some(rest.iter().fold(
+        (first, first),
+        |((min_x, min_y), (max_x, max_y)), &(x, y)| {
+            ((min_x.min(x), min_y.min(y)), (max_x.max(x), max_y.max(y)))
+        },
+    ))
+}
+
+/// The average position of a set of tiles, rounded to the closest tile.
+/// Returns `None` if `tiles` is empty.
+///
+/// # Examples
+/// ```
+/// use torchbearer::tile_set_centroid;
+///
+/// assert_eq!(tile_set_centroid(&[(0, 0), (4, 0), (4, 3)]), Some((3, 1)));
+/// ```
+pub fn tile_set_centroid(tiles: &[Point]) -> Option<Point> {
+    if tiles.is_empty() {
+        return None;
+    }
+
+    let (sum_x, sum_y) = tiles.iter().fold((0i64, 0i64), |(sum_x, sum_y), &(x, y)| {
+        (sum_x + x as i64, sum_y + y as i64)
+    });
+    let count = tiles.len() as f64;
+    Some((
+        (sum_x as f64 / count).round() as i32,
+        (sum_y as f64 / count).round() as i32,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        mirror_x, mirror_y, rotate_point_cw, tile_set_bounds, tile_set_centroid, Direction,
+        OutOfBounds,
+    };
+
+    #[test]
+    fn direction_all_offsets_are_distinct_neighboors() {
+        let offsets: Vec<_> = Direction::all()
+            .map(|direction| direction.offset())
+            .collect();
+
+        assert_eq!(offsets.len(), 8);
+        for (index, &(dx, dy)) in offsets.iter().enumerate() {
+            assert_eq!(dx.abs().max(dy.abs()), 1);
+            assert!(!offsets[..index].contains(&(dx, dy)));
+        }
+        assert!(Direction::cardinals().all(|direction| Direction::all().any(|d| d == direction)));
+        assert!(Direction::cardinals().all(|direction| {
+            let (dx, dy) = direction.offset();
+            dx == 0 || dy == 0
+        }));
+    }
+
+    #[test]
+    fn out_of_bounds_check() {
+        assert_eq!(OutOfBounds::check((0, 0), (3, 2)), Ok(()));
+        assert_eq!(OutOfBounds::check((2, 1), (3, 2)), Ok(()));
+
+        let error = OutOfBounds::check((-1, 1), (3, 2)).unwrap_err();
+        assert_eq!(error.position, (-1, 1));
+        assert_eq!(error.dimensions, (3, 2));
+        assert_eq!(
+            error.to_string(),
+            "(x, y) should be between (0,0) and (3, 2), got (-1, 1)."
+        );
+        assert!(OutOfBounds::check((3, 0), (3, 2)).is_err());
+    }
+
+    #[test]
+    fn rotate_four_times_is_identity() {
+        let (width, height) = (7, 4);
+        let point = (5, 1);
+
+        let once = rotate_point_cw(point, (width, height));
+        let twice = rotate_point_cw(once, (height, width));
+        let thrice = rotate_point_cw(twice, (width, height));
+        let back = rotate_point_cw(thrice, (height, width));
+
+        assert_eq!(once, (2, 5));
+        assert_eq!(twice, (1, 2));
+        assert_eq!(back, point);
+    }
+
+    #[test]
+    fn mirror_twice_is_identity() {
+        let point = (2, 3);
+
+        assert_eq!(mirror_x(point, 10), (7, 3));
+        assert_eq!(mirror_x(mirror_x(point, 10), 10), point);
+        assert_eq!(mirror_y(point, 5), (2, 1));
+        assert_eq!(mirror_y(mirror_y(point, 5), 5), point);
+    }
+
+    #[test]
+    fn tile_set_block_bounds_and_centroid() {
+        let block: Vec<_> = (2..5).flat_map(|x| (6..9).map(move |y| (x, y))).collect();
+
+        assert_eq!(tile_set_bounds(&block), Some(((2, 6), (4, 8))));
+        assert_eq!(tile_set_centroid(&block), Some((3, 7)));
+    }
+
+    #[test]
+    fn tile_set_empty() {
+        assert_eq!(tile_set_bounds(&[]), None);
+        assert_eq!(tile_set_centroid(&[]), None);
+    }
+}
@@ -0,0 +1,751 @@
+//! Ready-made map types, for when you don't want to implement the traits yourself.
+
+use crate::{
+    fov::{field_of_view, VisionMap},
+    path::PathMap,
+    Point,
+};
+
+/// A map that can both be walked and seen through, sharing its dimensions.
+///
+/// Every type implementing `GridMap` also implements [`PathMap`] and [`VisionMap`], so it works
+/// with both the pathfinding and the field of view functions. Implement the two traits directly
+/// instead if you need something else, like a map where the vision and walking grids differ in
+/// size.
+///
+/// With `GridMap` and one of the other traits both in scope, method calls like
+/// `map.dimensions()` become ambiguous: import only the trait you need, or call
+/// `PathMap::dimensions(&map)`.
+///
+/// # Examples
+/// ```
+/// use torchbearer::{
+///     fov::field_of_view, map::GridMap, path::astar_path_fourwaygrid, Point,
+/// };
+///
+/// struct Dungeon {
+///     walls: Vec<bool>,
+/// }
+///
+/// impl GridMap for Dungeon {
+///     fn dimensions(&self) -> (i32, i32) {
+///         (10, 10)
+///     }
+///
+///     fn is_walkable(&self, (x, y): Point) -> bool {
+///         !self.walls[(x + y * 10) as usize]
+///     }
+///
+///     fn is_transparent(&self, (x, y): Point) -> bool {
+///         !self.walls[(x + y * 10) as usize]
+///     }
+/// }
+///
+/// let dungeon = Dungeon {
+///     walls: vec![false; 100],
+/// };
+///
+/// assert!(astar_path_fourwaygrid(&dungeon, (0, 0), (9, 9)).is_some());
+/// assert_eq!(field_of_view(&dungeon, (5, 5), 0), vec![(5, 5)]);
+/// ```
+pub trait GridMap {
+    /// Dimension of your map, in grid size.
+    fn dimensions(&self) -> (i32, i32);
+    /// Wether it is possible or not to walk through the tile at position `(x, y)`.
+    fn is_walkable(&self, position: Point) -> bool;
+    /// Wether it is possible or not to see through the tile at position `(x, y)`.
+    fn is_transparent(&self, position: Point) -> bool;
+    /// How much it costs to walk into the tile at position `(x, y)`, `1.0` by default.
+    /// See [`PathMap::cost`].
+    fn cost(&self, _position: Point) -> f32 {
+        1.
+    }
+}
+
+impl<T: GridMap> PathMap for T {
+    fn dimensions(&self) -> (i32, i32) {
+        GridMap::dimensions(self)
+    }
+
+    fn is_walkable(&self, position: Point) -> bool {
+        GridMap::is_walkable(self, position)
+    }
+
+    fn cost(&self, position: Point) -> f32 {
+        GridMap::cost(self, position)
+    }
+}
+
+impl<T: GridMap> VisionMap for T {
+    fn dimensions(&self) -> (i32, i32) {
+        GridMap::dimensions(self)
+    }
+
+    fn is_transparent(&self, position: Point) -> bool {
+        GridMap::is_transparent(self, position)
+    }
+}
+
+/// How to read the booleans of a grid passed to [`grid_map`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellMeaning {
+    /// `true` means the cell is open: walkable and transparent.
+    Open,
+    /// `true` means the cell is blocked: neither walkable nor transparent.
+    Blocked,
+}
+
+/// A map backed by a vector of booleans, one per cell, in row-major order.
+/// A cell is either open (walkable and transparent) or blocked.
+///
+/// Implements both [`PathMap`] and [`VisionMap`]. Create it with [`grid_map`].
+#[derive(Debug, Clone)]
+pub struct VecGridMap {
+    width: i32,
+    height: i32,
+    open: Vec<bool>,
+}
+
+impl VecGridMap {
+    /// Is the cell at position `(x, y)` open, meaning walkable and transparent.
+    pub fn is_open(&self, (x, y): Point) -> bool {
+        self.open[(x + y * self.width) as usize]
+    }
+}
+
+/// Wraps an existing grid of booleans into a map implementing both [`PathMap`] and [`VisionMap`].
+///
+/// # Arguments
+///
+/// * `width` - The width of the map.
+/// * `height` - The height of the map.
+/// * `cells` - One boolean per cell, in row-major order (index is `x + y * width`).
+/// * `cell_means` - What a `true` cell stands for: an open cell, or a blocked one.
+///
+/// # Panics
+///
+/// Panics if `cells` doesn't contain exactly `width * height` elements.
+///
+/// # Examples
+/// ```
+/// use torchbearer::{
+///     fov::VisionMap,
+///     map::{grid_map, CellMeaning},
+///     path::PathMap,
+/// };
+///
+/// // Here, `true` is a wall.
+/// let walls = vec![false, true, false, false];
+/// let map = grid_map(2, 2, walls, CellMeaning::Blocked);
+///
+/// assert!(map.is_walkable((0, 0)));
+/// assert!(!map.is_transparent((1, 0)));
+/// ```
+pub fn grid_map(width: i32, height: i32, cells: Vec<bool>, cell_means: CellMeaning) -> VecGridMap {
+    if cells.len() != (width * height) as usize {
+        panic!(
+            "cells should contain {} elements for a map of ({}, {}), got {}.",
+            width * height,
+            width,
+            height,
+            cells.len()
+        );
+    }
+
+    let open = match cell_means {
+        CellMeaning::Open => cells,
+        CellMeaning::Blocked => cells.into_iter().map(|blocked| !blocked).collect(),
+    };
+
+    VecGridMap {
+        width,
+        height,
+        open,
+    }
+}
+
+impl GridMap for VecGridMap {
+    fn dimensions(&self) -> (i32, i32) {
+        (self.width, self.height)
+    }
+
+    fn is_walkable(&self, position: Point) -> bool {
+        self.is_open(position)
+    }
+
+    fn is_transparent(&self, position: Point) -> bool {
+        self.is_open(position)
+    }
+}
+
+/// A map borrowing a slice of booleans, one per cell, in row-major order. A `true` cell is
+/// open: walkable and transparent.
+///
+/// Implements both [`PathMap`] and [`VisionMap`], without copying the cells.
+///
+/// # Examples
+/// ```
+/// use torchbearer::{map::SliceMap, path::astar_path_fourwaygrid};
+///
+/// let tiles = vec![
+///     true, true, true,
+///     false, false, true,
+///     true, true, true,
+/// ];
+/// let map = SliceMap::new(&tiles, 3, 3);
+///
+/// let path = astar_path_fourwaygrid(&map, (0, 0), (0, 2)).unwrap();
+/// assert_eq!(path.len(), 7);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct SliceMap<'a> {
+    tiles: &'a [bool],
+    width: i32,
+    height: i32,
+}
+
+impl<'a> SliceMap<'a> {
+    /// Wraps `tiles`, one boolean per cell in row-major order (index is `x + y * width`).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tiles` doesn't contain exactly `width * height` elements.
+    pub fn new(tiles: &'a [bool], width: i32, height: i32) -> Self {
+        if tiles.len() != (width * height) as usize {
+            panic!(
+                "tiles should contain {} elements for a map of ({}, {}), got {}.",
+                width * height,
+                width,
+                height,
+                tiles.len()
+            );
+        }
+
+        SliceMap {
+            tiles,
+            width,
+            height,
+        }
+    }
+}
+
+impl<'a> GridMap for SliceMap<'a> {
+    fn dimensions(&self) -> (i32, i32) {
+        (self.width, self.height)
+    }
+
+    fn is_walkable(&self, (x, y): Point) -> bool {
+        self.tiles[(x + y * self.width) as usize]
+    }
+
+    fn is_transparent(&self, (x, y): Point) -> bool {
+        self.tiles[(x + y * self.width) as usize]
+    }
+}
+
+/// A map borrowing rows of booleans, where `rows[y][x]` is the cell at position `(x, y)`.
+/// A `true` cell is open: walkable and transparent.
+///
+/// The height of the map is the number of rows, and its width the length of the rows.
+/// Implements both [`PathMap`] and [`VisionMap`].
+///
+/// # Examples
+/// ```
+/// use torchbearer::{fov::field_of_view, map::RowsMap};
+///
+/// let rows = vec![
+///     vec![true, true, true, true],
+///     vec![true, false, true, true],
+///     vec![true, true, true, true],
+/// ];
+/// let map = RowsMap::new(&rows);
+///
+/// let visibles = field_of_view(&map, (0, 0), 5);
+/// assert!(visibles.contains(&(1, 1)));
+/// assert!(!visibles.contains(&(2, 2)));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct RowsMap<'a> {
+    rows: &'a [Vec<bool>],
+    width: i32,
+}
+
+impl<'a> RowsMap<'a> {
+    /// Wraps `rows`, `rows[y][x]` being the cell at position `(x, y)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rows don't all have the same length.
+    pub fn new(rows: &'a [Vec<bool>]) -> Self {
+        let width = rows.first().map_or(0, |row| row.len());
+        if let Some((y, row)) = rows.iter().enumerate().find(|(_, row)| row.len() != width) {
+            panic!(
+                "rows should all have a length of {}, got {} for row {}.",
+                width,
+                row.len(),
+                y
+            );
+        }
+
+        RowsMap {
+            rows,
+            width: width as i32,
+        }
+    }
+}
+
+impl<'a> GridMap for RowsMap<'a> {
+    fn dimensions(&self) -> (i32, i32) {
+        (self.width, self.rows.len() as i32)
+    }
+
+    fn is_walkable(&self, (x, y): Point) -> bool {
+        self.rows[y as usize][x as usize]
+    }
+
+    fn is_transparent(&self, (x, y): Point) -> bool {
+        self.rows[y as usize][x as usize]
+    }
+}
+
+/// A map where walkability and transparency are set independently, cell by cell.
+/// All the cells start walkable and transparent.
+///
+/// Implements both [`PathMap`] and [`VisionMap`].
+///
+/// # Examples
+/// ```
+/// use torchbearer::{fov::field_of_view, map::Grid, path::astar_path_fourwaygrid};
+///
+/// let mut grid = Grid::new(10, 10);
+/// for y in 0..9 {
+///     // A wall, with a glass pane that blocks walking but not vision.
+///     grid.set_walkable((5, y), false);
+///     grid.set_transparent((5, y), y == 4);
+/// }
+///
+/// let path = astar_path_fourwaygrid(&grid, (0, 0), (9, 0)).unwrap();
+/// assert!(path.contains(&(5, 9)));
+///
+/// let visibles = field_of_view(&grid, (4, 4), 3);
+/// assert!(visibles.contains(&(6, 4)));
+/// assert!(!visibles.contains(&(6, 2)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid {
+    width: i32,
+    height: i32,
+    walkable: Vec<bool>,
+    transparent: Vec<bool>,
+}
+
+impl Grid {
+    /// Creates a grid of `width * height` cells, all walkable and transparent.
+    pub fn new(width: i32, height: i32) -> Self {
+        let cell_count = (width * height) as usize;
+        Grid {
+            width,
+            height,
+            walkable: vec![true; cell_count],
+            transparent: vec![true; cell_count],
+        }
+    }
+
+    /// Sets whether the cell at position `(x, y)` can be walked on.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `(x, y)` is out of the grid bounds.
+    pub fn set_walkable(&mut self, position: Point, walkable: bool) {
+        let index = self.index(position);
+        self.walkable[index] = walkable;
+    }
+
+    /// Sets whether the cell at position `(x, y)` can be seen through.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `(x, y)` is out of the grid bounds.
+    pub fn set_transparent(&mut self, position: Point, transparent: bool) {
+        let index = self.index(position);
+        self.transparent[index] = transparent;
+    }
+
+    fn index(&self, (x, y): Point) -> usize {
+        if x < 0 || y < 0 || x >= self.width || y >= self.height {
+            panic!(
+                "(x, y) should be between (0,0) and ({}, {}), got ({}, {}).",
+                self.width, self.height, x, y
+            );
+        }
+        (x + y * self.width) as usize
+    }
+}
+
+impl GridMap for Grid {
+    fn dimensions(&self) -> (i32, i32) {
+        (self.width, self.height)
+    }
+
+    fn is_walkable(&self, position: Point) -> bool {
+        self.walkable[self.index(position)]
+    }
+
+    fn is_transparent(&self, position: Point) -> bool {
+        self.transparent[self.index(position)]
+    }
+}
+
+/// A compact grid of booleans, one bit per cell, for instance to remember which tiles were
+/// already explored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitGrid {
+    width: i32,
+    height: i32,
+    bits: Vec<u64>,
+}
+
+impl BitGrid {
+    /// Creates a grid of `width * height` cells, all set to `false`.
+    pub fn new(width: i32, height: i32) -> Self {
+        let cell_count = (width * height) as usize;
+        BitGrid {
+            width,
+            height,
+            bits: vec![0; cell_count.div_ceil(64)],
+        }
+    }
+
+    /// Dimension of the grid, in cells.
+    pub fn dimensions(&self) -> (i32, i32) {
+        (self.width, self.height)
+    }
+
+    /// Value of the cell at position `(x, y)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `(x, y)` is out of the grid bounds.
+    pub fn get(&self, position: Point) -> bool {
+        let index = self.index(position);
+        self.bits[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    /// Sets the value of the cell at position `(x, y)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `(x, y)` is out of the grid bounds.
+    pub fn set(&mut self, position: Point, value: bool) {
+        let index = self.index(position);
+        if value {
+            self.bits[index / 64] |= 1 << (index % 64);
+        } else {
+            self.bits[index / 64] &= !(1 << (index % 64));
+        }
+    }
+
+    /// Sets all the cells to `false`.
+    pub fn clear(&mut self) {
+        self.bits.fill(0);
+    }
+
+    fn index(&self, (x, y): Point) -> usize {
+        if x < 0 || y < 0 || x >= self.width || y >= self.height {
+            panic!(
+                "(x, y) should be between (0,0) and ({}, {}), got ({}, {}).",
+                self.width, self.height, x, y
+            );
+        }
+        (x + y * self.width) as usize
+    }
+}
+
+/// Keeps track of what is currently visible and what was seen before, to draw explored but
+/// out of sight tiles differently.
+///
+/// # Examples
+/// ```
+/// use torchbearer::map::{grid_map, CellMeaning, FogOfWar};
+///
+/// let map = grid_map(20, 20, vec![true; 400], CellMeaning::Open);
+/// let mut fog = FogOfWar::new(20, 20);
+///
+/// fog.update(&map, (2, 2), 3);
+/// fog.update(&map, (15, 15), 3);
+///
+/// assert!(fog.is_visible((15, 15)));
+/// assert!(!fog.is_visible((2, 2)));
+/// assert!(fog.is_explored((2, 2)));
+/// assert!(!fog.is_explored((10, 2)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FogOfWar {
+    visible: BitGrid,
+    explored: BitGrid,
+}
+
+impl FogOfWar {
+    /// Creates the fog of war of a `width * height` map, where nothing was seen yet.
+    pub fn new(width: i32, height: i32) -> Self {
+        FogOfWar {
+            visible: BitGrid::new(width, height),
+            explored: BitGrid::new(width, height),
+        }
+    }
+
+    /// Recomputes what is visible from `from`, and marks it as explored.
+    /// The tiles visible before the update and not anymore stay explored.
+    ///
+    /// # Arguments
+    ///
+    /// * `map` - A struct implementing the `VisionMap` trait.
+    /// * `from` - The origin/center of the field of vision.
+    /// * `radius` - How far the vision should go.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from` is out of the map bounds, or if the map doesn't have the dimensions
+    /// of the fog of war.
+    pub fn update<T: VisionMap>(&mut self, map: &T, from: Point, radius: i32) {
+        let (width, height) = self.visible.dimensions();
+        let (map_width, map_height) = map.dimensions();
+        if (width, height) != (map_width, map_height) {
+            panic!(
+                "map should have the dimensions of the fog of war ({}, {}), got ({}, {}).",
+                width, height, map_width, map_height
+            );
+        }
+
+        self.visible.clear();
+        for position in field_of_view(map, from, radius) {
+            self.visible.set(position, true);
+            self.explored.set(position, true);
+        }
+    }
+
+    /// Is the tile at position `(x, y)` visible since the last update.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `(x, y)` is out of the map bounds.
+    pub fn is_visible(&self, position: Point) -> bool {
+        self.visible.get(position)
+    }
+
+    /// Was the tile at position `(x, y)` ever visible.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `(x, y)` is out of the map bounds.
+    pub fn is_explored(&self, position: Point) -> bool {
+        self.explored.get(position)
+    }
+
+    /// The tiles visible since the last update.
+    pub fn visible(&self) -> &BitGrid {
+        &self.visible
+    }
+
+    /// The tiles that were ever visible, for instance to pass to
+    /// [`explore_step`](crate::path::explore_step).
+    pub fn explored(&self) -> &BitGrid {
+        &self.explored
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fov::VisionMap, path::PathMap};
+
+    use crate::Point;
+
+    use super::{grid_map, BitGrid, CellMeaning, FogOfWar, Grid, RowsMap, SliceMap};
+
+    #[test]
+    fn grid_map_true_means_open() {
+        let map = grid_map(2, 2, vec![true, false, true, true], CellMeaning::Open);
+
+        assert!(map.is_walkable((0, 0)));
+        assert!(map.is_transparent((0, 0)));
+        assert!(!map.is_walkable((1, 0)));
+        assert!(!map.is_transparent((1, 0)));
+        assert!(map.is_walkable((1, 1)));
+    }
+
+    #[test]
+    fn grid_map_true_means_blocked() {
+        let map = grid_map(2, 2, vec![true, false, true, true], CellMeaning::Blocked);
+
+        assert!(!map.is_walkable((0, 0)));
+        assert!(!map.is_transparent((0, 0)));
+        assert!(map.is_walkable((1, 0)));
+        assert!(map.is_transparent((1, 0)));
+        assert!(!map.is_walkable((1, 1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "cells should contain 4 elements for a map of (2, 2), got 3.")]
+    fn grid_map_wrong_size_panics() {
+        grid_map(2, 2, vec![true; 3], CellMeaning::Open);
+    }
+
+    #[test]
+    fn grid_map_implements_both_traits() {
+        use super::GridMap;
+
+        struct Swamp;
+
+        impl GridMap for Swamp {
+            fn dimensions(&self) -> (i32, i32) {
+                (4, 3)
+            }
+
+            fn is_walkable(&self, (x, _y): Point) -> bool {
+                x != 1
+            }
+
+            fn is_transparent(&self, (x, _y): Point) -> bool {
+                x != 2
+            }
+
+            fn cost(&self, _position: Point) -> f32 {
+                3.
+            }
+        }
+
+        assert_eq!(PathMap::dimensions(&Swamp), (4, 3));
+        assert_eq!(VisionMap::dimensions(&Swamp), (4, 3));
+        assert!(!PathMap::is_walkable(&Swamp, (1, 0)));
+        assert!(VisionMap::is_transparent(&Swamp, (1, 0)));
+        assert!(PathMap::is_walkable(&Swamp, (2, 0)));
+        assert!(!VisionMap::is_transparent(&Swamp, (2, 0)));
+        assert_eq!(PathMap::cost(&Swamp, (0, 0)), 3.);
+    }
+
+    #[test]
+    fn slice_map_reads_the_slice() {
+        let tiles = [true, false, true, true, true, false];
+        let map = SliceMap::new(&tiles, 3, 2);
+
+        assert_eq!(PathMap::dimensions(&map), (3, 2));
+        assert!(map.is_walkable((0, 0)));
+        assert!(!map.is_walkable((1, 0)));
+        assert!(map.is_transparent((1, 1)));
+        assert!(!map.is_transparent((2, 1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "tiles should contain 6 elements for a map of (3, 2), got 5.")]
+    fn slice_map_wrong_size_panics() {
+        SliceMap::new(&[true; 5], 3, 2);
+    }
+
+    #[test]
+    fn rows_map_indexes_rows_then_columns() {
+        let rows = vec![vec![true, false, true], vec![false, true, true]];
+        let map = RowsMap::new(&rows);
+
+        assert_eq!(VisionMap::dimensions(&map), (3, 2));
+        assert!(!map.is_walkable((1, 0)));
+        assert!(!map.is_transparent((0, 1)));
+        assert!(map.is_walkable((2, 1)));
+    }
+
+    #[test]
+    fn rows_map_empty() {
+        let map = RowsMap::new(&[]);
+
+        assert_eq!(PathMap::dimensions(&map), (0, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "rows should all have a length of 3, got 2 for row 1.")]
+    fn rows_map_uneven_rows_panics() {
+        RowsMap::new(&[vec![true; 3], vec![true; 2], vec![true; 3]]);
+    }
+
+    #[test]
+    fn grid_walkable_and_transparent_are_independent() {
+        let mut grid = Grid::new(3, 3);
+        assert!(grid.is_walkable((1, 1)));
+        assert!(grid.is_transparent((1, 1)));
+
+        grid.set_walkable((1, 1), false);
+        grid.set_transparent((2, 1), false);
+
+        assert!(!grid.is_walkable((1, 1)));
+        assert!(grid.is_transparent((1, 1)));
+        assert!(grid.is_walkable((2, 1)));
+        assert!(!grid.is_transparent((2, 1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "(x, y) should be between (0,0) and (3, 3), got (0, 3).")]
+    fn grid_out_of_bounds_panics() {
+        Grid::new(3, 3).set_walkable((0, 3), false);
+    }
+
+    #[test]
+    fn bit_grid_get_and_set() {
+        let mut grid = BitGrid::new(10, 10);
+        assert!(!grid.get((3, 7)));
+
+        grid.set((3, 7), true);
+        grid.set((9, 9), true);
+        assert!(grid.get((3, 7)));
+        assert!(grid.get((9, 9)));
+        assert!(!grid.get((4, 7)));
+
+        grid.set((3, 7), false);
+        assert!(!grid.get((3, 7)));
+    }
+
+    #[test]
+    #[should_panic(expected = "(x, y) should be between (0,0) and (10, 10), got (10, 0).")]
+    fn bit_grid_out_of_bounds_panics() {
+        BitGrid::new(10, 10).get((10, 0));
+    }
+
+    #[test]
+    fn bit_grid_clear() {
+        let mut grid = BitGrid::new(10, 10);
+        grid.set((3, 7), true);
+        grid.set((9, 9), true);
+
+        grid.clear();
+
+        assert_eq!(grid, BitGrid::new(10, 10));
+    }
+
+    #[test]
+    fn fog_of_war_remembers_explored_tiles() {
+        // A wall splits the map in two, with a door at (5, 9).
+        let cells = (0..100)
+            .map(|index| index % 10 != 5 || index == 95)
+            .collect();
+        let map = grid_map(10, 10, cells, CellMeaning::Open);
+        let mut fog = FogOfWar::new(10, 10);
+
+        fog.update(&map, (2, 2), 10);
+        assert!(fog.is_visible((2, 2)));
+        assert!(fog.is_visible((5, 2)));
+        assert!(!fog.is_visible((7, 2)));
+        assert!(!fog.is_explored((7, 2)));
+
+        fog.update(&map, (7, 2), 10);
+        assert!(fog.is_visible((7, 2)));
+        assert!(!fog.is_visible((2, 2)));
+        assert!(fog.is_explored((2, 2)));
+        assert!(fog.is_explored((7, 2)));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "map should have the dimensions of the fog of war (10, 10), got (2, 2)."
+    )]
+    fn fog_of_war_wrong_dimensions_panics() {
+        let map = grid_map(2, 2, vec![true; 4], CellMeaning::Open);
+        FogOfWar::new(10, 10).update(&map, (0, 0), 3);
+    }
+}
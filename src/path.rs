@@ -1,8 +1,16 @@
 //! Collection of utility functions to find path.
 
-use std::{cmp::Ordering, collections::BinaryHeap};
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, VecDeque},
+};
 
-use crate::Point;
+use crate::{
+    bresenham::{BresenhamLine, Rings},
+    fov::{field_of_view, VisionMap},
+    map::BitGrid,
+    Direction, OutOfBounds, Point,
+};
 
 pub type NodeId = usize;
 
@@ -13,6 +21,15 @@ pub trait PathMap {
     /// Wether it is possible or not to walk through the tile at position `(x, y)`.
     /// Used by pathfinding algorithm.
     fn is_walkable(&self, position: Point) -> bool;
+    /// How much it costs to walk into the tile at position `(x, y)`, `1.0` by default.
+    /// The provided graphs multiply the cost of each step by the cost of its destination,
+    /// so a swamp could cost `3.0` and a road `1.0`.
+    ///
+    /// Keep it at `1.0` or more: the heuristics assume a step costs at least as much as on a
+    /// plain tile, and cheaper tiles could lead to paths that are not the shortest.
+    fn cost(&self, _position: Point) -> f32 {
+        1.
+    }
 }
 
 /// An A* pathfinding implementation for a grid base map, where diagonal movements are disabled.
@@ -75,29 +92,285 @@ pub trait PathMap {
 ///     // (…)
 /// }
 /// ```
-pub fn astar_path_fourwaygrid<T: PathMap>(map: &T, from: Point, to: Point) -> Option<Vec<Point>> {
-    fn assert_in_bounds<T: PathMap>(map: &T, (x, y): Point) {
-        let (width, height) = map.dimensions();
-        if x < 0 || y < 0 || x >= width || y >= height {
-            panic!(
-                "(x, y) should be between (0,0) and ({}, {}), got ({}, {}).",
-                width, height, x, y
-            );
-        }
-    }
+pub fn astar_path_fourwaygrid<T: PathMap + ?Sized>(
+    map: &T,
+    from: Point,
+    to: Point,
+) -> Option<Vec<Point>> {
+    try_astar_path_fourwaygrid(map, from, to).unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Same as [`astar_path_fourwaygrid`], but returns an error instead of panicking if `from` or
+/// `to` are out of bounds of the map.
+///
+/// # Arguments
+///
+/// * `map` - a struct implementing the `Map` trait.
+/// * `from` - the origin.
+/// * `to` - the destination.
+///
+/// # Examples
+/// ```
+/// use torchbearer::{map::Grid, path::try_astar_path_fourwaygrid, OutOfBounds};
+///
+/// let map = Grid::new(10, 10);
+///
+/// assert!(try_astar_path_fourwaygrid(&map, (0, 0), (9, 9)).unwrap().is_some());
+/// assert_eq!(
+///     try_astar_path_fourwaygrid(&map, (0, 0), (10, 9)),
+///     Err(OutOfBounds {
+///         position: (10, 9),
+///         dimensions: (10, 10),
+///     })
+/// );
+/// ```
+pub fn try_astar_path_fourwaygrid<T: PathMap + ?Sized>(
+    map: &T,
+    from: Point,
+    to: Point,
+) -> Result<Option<Vec<Point>>, OutOfBounds> {
+    OutOfBounds::check(from, map.dimensions())?;
+    OutOfBounds::check(to, map.dimensions())?;
+
+    let graph = FourWayGridGraph::new(map);
+    Ok(
+        astar_path(&graph, graph.point_to_index(from), graph.point_to_index(to)).map(|indices| {
+            indices
+                .into_iter()
+                .map(|index| graph.index_to_point(index))
+                .collect()
+        }),
+    )
+}
+
+/// Same as [`astar_path_fourwaygrid`], for maps stored behind a trait object, like a
+/// `Box<dyn PathMap>`.
+///
+/// # Panics
+///
+/// Panics if `from` or `to` are out of bounds of the map.
+pub fn astar_path_fourwaygrid_dyn(map: &dyn PathMap, from: Point, to: Point) -> Option<Vec<Point>> {
+    astar_path_fourwaygrid(map, from, to)
+}
 
-    assert_in_bounds(map, from);
-    assert_in_bounds(map, to);
+/// Check if there is a path between `from` and `to` on a four way grid, without building it.
+///
+/// # Panics
+///
+/// Panics if `from` or `to` are out of bounds of the map.
+pub fn is_reachable_fourwaygrid<T: PathMap + ?Sized>(map: &T, from: Point, to: Point) -> bool {
+    assert_point_in_bounds(map, from);
+    assert_point_in_bounds(map, to);
 
     let graph = FourWayGridGraph::new(map);
-    astar_path(&graph, graph.point_to_index(from), graph.point_to_index(to)).map(|indices| {
+    is_reachable(&graph, graph.point_to_index(from), graph.point_to_index(to))
+}
+
+/// An A* pathfinding implementation for a grid base map, where diagonal movements are disabled,
+/// and where walking next to walls is penalized. Units following the path will prefer open tiles
+/// instead of scraping along walls.
+///
+/// Each tile costs an extra `wall_proximity_penalty` for each of its eight neighboors that is not
+/// walkable (or out of the map). With a penalty of `0.`, this is the same as [`astar_path_fourwaygrid`].
+///
+/// # Arguments
+///
+/// * `map` - a struct implementing the `Map` trait.
+/// * `from` - the origin.
+/// * `to` - the destination.
+/// * `wall_proximity_penalty` - the extra cost for each wall around a tile.
+///
+/// # Panics
+///
+/// Panics if `from` or `to` are out of bounds of the map.
+pub fn astar_path_centered<T: PathMap>(
+    map: &T,
+    from: Point,
+    to: Point,
+    wall_proximity_penalty: f32,
+) -> Option<Vec<Point>> {
+    assert_point_in_bounds(map, from);
+    assert_point_in_bounds(map, to);
+
+    let graph = CenteredGridGraph {
+        graph: FourWayGridGraph::new(map),
+        wall_proximity_penalty,
+    };
+    astar_path(
+        &graph,
+        graph.graph.point_to_index(from),
+        graph.graph.point_to_index(to),
+    )
+    .map(|indices| {
+        indices
+            .into_iter()
+            .map(|index| graph.graph.index_to_point(index))
+            .collect()
+    })
+}
+
+/// An A* pathfinding implementation for a grid base map, where diagonal movements are disabled,
+/// and where changing direction costs extra. Vehicles following the path will prefer long
+/// straight lines to zig-zags of the same length.
+///
+/// Each step that doesn't go in the same direction as the previous one costs an extra
+/// `turn_penalty`. The search keeps track of the direction each tile was entered from, so a tile
+/// can be part of the best path whatever the direction it is crossed in. With a penalty of `0.`,
+/// the path is as short as one found by [`astar_path_fourwaygrid`].
+///
+/// # Arguments
+///
+/// * `map` - a struct implementing the `Map` trait.
+/// * `from` - the origin.
+/// * `to` - the destination.
+/// * `turn_penalty` - the extra cost of each change of direction.
+///
+/// # Panics
+///
+/// Panics if `from` or `to` are out of bounds of the map.
+///
+/// # Examples
+/// ```
+/// use torchbearer::{map::Grid, path::astar_path_turn_penalty};
+///
+/// let map = Grid::new(10, 10);
+/// let path = astar_path_turn_penalty(&map, (0, 0), (3, 2), 1.).unwrap();
+///
+/// // A single turn, either east then south or south then east.
+/// let east_first = [(0, 0), (1, 0), (2, 0), (3, 0), (3, 1), (3, 2)];
+/// let south_first = [(0, 0), (0, 1), (0, 2), (1, 2), (2, 2), (3, 2)];
+/// assert!(path == east_first || path == south_first);
+/// ```
+pub fn astar_path_turn_penalty<T: PathMap>(
+    map: &T,
+    from: Point,
+    to: Point,
+    turn_penalty: f32,
+) -> Option<Vec<Point>> {
+    assert_point_in_bounds(map, from);
+    assert_point_in_bounds(map, to);
+
+    let graph = TurnPenaltyGridGraph {
+        graph: FourWayGridGraph::new(map),
+        turn_penalty,
+    };
+    let to_index = graph.graph.point_to_index(to);
+    let heuristic = |node| graph.graph.heuristic(graph.tile(node), to_index);
+    let is_goal = |node| graph.tile(node) == to_index;
+    let from_node = graph.node(
+        graph.graph.point_to_index(from),
+        TurnPenaltyGridGraph::<T>::START,
+    );
+
+    match astar_search(&graph, from_node, heuristic, is_goal, SearchLimits::NONE) {
+        SearchEnd::Found(tree) => Some(
+            tree.into_path()
+                .into_iter()
+                .map(|node| graph.graph.index_to_point(graph.tile(node)))
+                .collect(),
+        ),
+        SearchEnd::Unreachable(_) | SearchEnd::LimitReached => None,
+    }
+}
+
+/// An A* pathfinding implementation for a grid base map, where diagonal movements are disabled,
+/// for agents wider than a tile. A tile can only be stepped on if all the tiles within `clearance`
+/// (in Chebyshev distance, so a square of side `2 * clearance + 1`) are walkable.
+///
+/// The map is not modified: a clearance field is precomputed before the search.
+/// With a clearance of `0`, this is the same as [`astar_path_fourwaygrid`].
+///
+/// # Arguments
+///
+/// * `map` - a struct implementing the `Map` trait.
+/// * `from` - the origin.
+/// * `to` - the destination.
+/// * `clearance` - how many tiles around the agent need to be free.
+///
+/// # Panics
+///
+/// Panics if `from` or `to` are out of bounds of the map.
+pub fn astar_path_with_clearance<T: PathMap>(
+    map: &T,
+    from: Point,
+    to: Point,
+    clearance: i32,
+) -> Option<Vec<Point>> {
+    assert_point_in_bounds(map, from);
+    assert_point_in_bounds(map, to);
+
+    let graph = ClearanceGridGraph {
+        graph: FourWayGridGraph::new(map),
+        clearances: clearance_field(map),
+        clearance,
+    };
+    astar_path(
+        &graph,
+        graph.graph.point_to_index(from),
+        graph.graph.point_to_index(to),
+    )
+    .map(|indices| {
         indices
             .into_iter()
-            .map(|index| graph.index_to_point(index))
+            .map(|index| graph.graph.index_to_point(index))
             .collect()
     })
 }
 
+/// Compute, for each tile, how many tiles around it are free in all directions.
+/// A blocked tile has a clearance of `-1`, a walkable tile next to a wall or the map's edge
+/// a clearance of `0`, and so on.
+///
+/// It's a Chebyshev distance transform to the closest blocked tile, done in two passes.
+fn clearance_field<T: PathMap>(map: &T) -> Vec<i32> {
+    let (width, height) = map.dimensions();
+    let mut distances = Vec::with_capacity((width * height) as usize);
+    for y in 0..height {
+        for x in 0..width {
+            let distance = if map.is_walkable((x, y)) {
+                // Outside of the map counts as blocked.
+                (x + 1).min(y + 1).min(width - x).min(height - y)
+            } else {
+                0
+            };
+            distances.push(distance);
+        }
+    }
+
+    let mut relax = |x: i32, y: i32, neighboors: &[Point]| {
+        let index = (x + y * width) as usize;
+        for &(nx, ny) in neighboors {
+            if nx >= 0 && ny >= 0 && nx < width && ny < height {
+                let candidate = distances[(nx + ny * width) as usize] + 1;
+                if candidate < distances[index] {
+                    distances[index] = candidate;
+                }
+            }
+        }
+    };
+
+    for y in 0..height {
+        for x in 0..width {
+            relax(
+                x,
+                y,
+                &[(x - 1, y - 1), (x, y - 1), (x + 1, y - 1), (x - 1, y)],
+            );
+        }
+    }
+    for y in (0..height).rev() {
+        for x in (0..width).rev() {
+            relax(
+                x,
+                y,
+                &[(x + 1, y + 1), (x, y + 1), (x - 1, y + 1), (x + 1, y)],
+            );
+        }
+    }
+
+    distances.into_iter().map(|distance| distance - 1).collect()
+}
+
 /// An A* pathfinding implementation for a grid base map.
 /// Returns an optional vector containing the several points on the map to walk through, including the origin and destination.
 ///
@@ -158,10 +431,11 @@ pub fn astar_path_fourwaygrid<T: PathMap>(map: &T, from: Point, to: Point) -> Op
 ///
 /// // Use one of the pre-made graphs (good for simple use cases), or implement your own.
 /// let graph = FourWayGridGraph::new(&sample_map);
-/// let from = (1 + 1 * width) as usize; // position to index
-/// let to = (3 + 8 * width) as usize; // position to index
+/// let from = graph.point_to_index((1, 1));
+/// let to = graph.point_to_index((3, 8));
 ///
 /// if let Some(path) = astar_path(&graph, to, from) {
+///     let positions: Vec<_> = path.into_iter().map(|index| graph.index_to_point(index)).collect();
 ///     // (…)
 /// }
 /// ```
@@ -170,333 +444,4426 @@ pub fn astar_path<T: Graph>(
     from_index: NodeId,
     to_index: NodeId,
 ) -> Option<Vec<NodeId>> {
-    fn assert_in_bounds<T: Graph>(graph: &T, index: NodeId) {
-        if index >= graph.node_count() {
-            panic!(
-                "Index {} is out of bounds for a graph of size {}.",
-                index,
-                graph.node_count()
-            );
-        }
-    }
-    assert_in_bounds(graph, from_index);
-    assert_in_bounds(graph, to_index);
-
-    let capacity = graph.node_count() / 2;
-    let mut frontier = BinaryHeap::with_capacity(capacity);
-
-    frontier.push(State {
-        cost: 0.,
-        item: from_index,
-    });
+    assert_index_in_bounds(graph, from_index);
+    assert_index_in_bounds(graph, to_index);
 
-    let mut came_from: Vec<Option<usize>> = vec![None; graph.node_count()];
-    let mut costs: Vec<Option<f32>> = vec![None; graph.node_count()];
-    costs[from_index] = Some(0.);
-    let mut neighboors: Vec<NodeId> = Vec::with_capacity(4);
+    astar_path_with_cost(graph, from_index, to_index).map(|(path, _cost)| path)
+}
 
-    let mut to_cost = 0.;
+/// Same as [`astar_path`], but also returns the total cost of the path: the sum of the
+/// [`Graph::cost_between`] of each step.
+///
+/// # Arguments
+///
+/// * `graph` - a struct implementing the `Graph` trait.
+/// * `from_index` - the origin.
+/// * `to_index` - the destination.
+///
+/// # Panics
+///
+/// Panics if `from_index` or `to_index` are out of bounds of the graph.
+pub fn astar_path_with_cost<T: Graph>(
+    graph: &T,
+    from_index: NodeId,
+    to_index: NodeId,
+) -> Option<(Vec<NodeId>, f32)> {
+    assert_index_in_bounds(graph, from_index);
+    assert_index_in_bounds(graph, to_index);
 
-    while let Some(State {
-        item: current_index,
-        cost: current_cost,
-    }) = frontier.pop()
-    {
-        if current_index == to_index {
-            to_cost = current_cost;
-            break;
+    match astar_search(
+        graph,
+        from_index,
+        |index| graph.heuristic(index, to_index),
+        |index| index == to_index,
+        SearchLimits::NONE,
+    ) {
+        SearchEnd::Found(tree) => {
+            let cost = tree.cost;
+            Some((tree.into_path(), cost))
         }
+        SearchEnd::Unreachable(_) | SearchEnd::LimitReached => None,
+    }
+}
 
-        neighboors.clear();
-        graph.neighboors(current_index, &mut neighboors);
-        for &next_index in neighboors.iter() {
-            let cost_so_far = costs[current_index].unwrap();
-            let new_cost = cost_so_far + graph.cost_between(current_index, next_index);
+/// Find a path going through each of the `waypoints`, in order, for instance for a patrol
+/// route. Returns `None` if one of the legs between two consecutive waypoints can't be walked,
+/// or if there are no waypoints.
+///
+/// The path of each leg is found with [`astar_path`], and the legs are joined together without
+/// repeating the waypoints where they meet.
+///
+/// # Arguments
+///
+/// * `graph` - a struct implementing the `Graph` trait.
+/// * `waypoints` - the nodes to go through, in order.
+///
+/// # Panics
+///
+/// Panics if one of the waypoints is out of bounds of the graph.
+pub fn astar_path_through<T: Graph>(graph: &T, waypoints: &[NodeId]) -> Option<Vec<NodeId>> {
+    let (&first, _) = waypoints.split_first()?;
+    assert_index_in_bounds(graph, first);
 
-            if costs[next_index].is_none() || new_cost < costs[next_index].unwrap() {
-                let priority = new_cost + graph.heuristic(next_index, to_index);
-                frontier.push(State {
-                    cost: priority,
-                    item: next_index,
-                });
-                came_from[next_index] = Some(current_index);
-                costs[next_index] = Some(new_cost);
-            }
-        }
+    let mut path = vec![first];
+    for leg in waypoints.windows(2) {
+        let leg_path = astar_path(graph, leg[0], leg[1])?;
+        // The first node of the leg is the last one of the path so far.
+        path.extend_from_slice(&leg_path[1..]);
     }
 
-    reconstruct_path(from_index, to_index, came_from, to_cost)
+    Some(path)
 }
 
-fn reconstruct_path(
-    from: NodeId,
-    to: NodeId,
-    came_from: Vec<Option<NodeId>>,
-    cost: f32,
-) -> Option<Vec<NodeId>> {
-    let mut current = Some(to);
-    let target_index = from;
-
-    let mut path = Vec::with_capacity((cost.floor() + 2.0) as usize);
+/// Same as [`astar_path`], but returns an iterator over the nodes of the path, from the origin
+/// to the destination, instead of collecting them in a vector.
+///
+/// # Arguments
+///
+/// * `graph` - a struct implementing the `Graph` trait.
+/// * `from_index` - the origin.
+/// * `to_index` - the destination.
+///
+/// # Panics
+///
+/// Panics if `from_index` or `to_index` are out of bounds of the graph.
+pub fn astar_path_iter<T: Graph>(
+    graph: &T,
+    from_index: NodeId,
+    to_index: NodeId,
+) -> Option<PathIter> {
+    assert_index_in_bounds(graph, from_index);
+    assert_index_in_bounds(graph, to_index);
 
-    while current != Some(target_index) {
-        if let Some(position) = current {
-            path.push(position);
-            current = if let Some(entry) = came_from[position] {
-                Some(entry)
-            } else {
-                return None;
-            }
-        } else {
-            return None;
-        }
+    match astar_search(
+        graph,
+        from_index,
+        |index| graph.heuristic(index, to_index),
+        |index| index == to_index,
+        SearchLimits::NONE,
+    ) {
+        SearchEnd::Found(tree) => Some(PathIter::new(tree)),
+        SearchEnd::Unreachable(_) | SearchEnd::LimitReached => None,
     }
-    path.push(target_index);
-    path.reverse();
-    Some(path)
 }
 
-struct State<C: PartialOrd, T> {
-    cost: C,
-    item: T,
+/// An iterator over the nodes of a path, from the origin to the destination.
+/// Created by [`astar_path_iter`].
+///
+/// The links found by the search go backward, from each node to the previous one. They are
+/// reversed in place when creating the iterator, so walking the path doesn't allocate.
+#[derive(Debug, Clone)]
+pub struct PathIter {
+    links: Vec<Option<NodeId>>,
+    next: Option<NodeId>,
+    remaining: usize,
 }
-impl<C: PartialOrd, T> PartialEq for State<C, T> {
-    fn eq(&self, other: &Self) -> bool {
-        self.cost.eq(&other.cost)
+
+impl PathIter {
+    fn new(tree: SearchTree) -> Self {
+        let SearchTree {
+            from,
+            goal,
+            mut came_from,
+            ..
+        } = tree;
+
+        let mut following = None;
+        let mut current = goal;
+        let mut remaining = 1;
+        while current != from {
+            let previous = came_from[current].unwrap();
+            came_from[current] = following;
+            following = Some(current);
+            current = previous;
+            remaining += 1;
+        }
+        came_from[from] = following;
+
+        PathIter {
+            links: came_from,
+            next: Some(from),
+            remaining,
+        }
     }
 }
 
-impl<C: PartialOrd, T> Eq for State<C, T> {}
+impl Iterator for PathIter {
+    type Item = NodeId;
 
-// The priority queue depends on `Ord`.
-// Explicitly implement the trait so the queue becomes a min-heap
-// instead of a max-heap.
-impl<C: PartialOrd, T> Ord for State<C, T> {
-    fn cmp(&self, other: &State<C, T>) -> Ordering {
-        // Notice that the we flip the ordering on costs.
-        // In case of a tie we compare positions - this step is necessary
-        // to make implementations of `PartialEq` and `Ord` consistent.
-        other
-            .cost
-            .partial_cmp(&self.cost)
-            .unwrap_or(Ordering::Equal)
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+        self.next = self.links[current];
+        self.remaining -= 1;
+        Some(current)
     }
-}
 
-// `PartialOrd` needs to be implemented as well.
-impl<C: PartialOrd, T> PartialOrd for State<C, T> {
-    fn partial_cmp(&self, other: &State<C, T>) -> Option<Ordering> {
-        Some(self.cmp(other))
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
     }
 }
 
-/// A graph for the A* algorithm. This is intended for a grid based representation, where each
-/// node would be a square on the map.
-pub trait Graph {
-    /// The amount of nodes in the graph, used to create correctly sized vectors.
-    fn node_count(&self) -> usize;
-
-    /// The cost between two points. A higher cost could represent a hard to cross terrain.
-    /// If normal terrain would cost 1 to go from a to be, climbing a mountain side could cost 2.
-    fn cost_between(&self, a: NodeId, b: NodeId) -> f32;
+impl ExactSizeIterator for PathIter {}
 
-    /// How close we are from our target.
-    /// See <https://www.redblobgames.com/pathfinding/a-star/introduction.html#greedy-best-first>
-    /// for more details about how it is useful.
-    fn heuristic(&self, a: NodeId, b: NodeId) -> f32;
+/// The result of a search that can give up before reaching its destination.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchOutcome {
+    /// A path was found, including the start and the end.
+    Found(Vec<NodeId>),
+    /// Every reachable node was explored without finding the destination.
+    Unreachable,
+    /// The search gave up after expanding the maximum number of nodes.
+    LimitReached,
+}
 
-    /// From point a, where can you go. Create a list of all possible neighboors.
-    /// No need to filter the walkable ones, or the one in bounds: the algorithm
-    /// does it later for optimisation purposes.
-    ///
-    /// # Arguments
-    ///
-    /// * `a` - the position whose neighboors you are looking for.
-    /// * `into` - push the neighboors into this vector.
-    ///   No need to clear explicitely, as `clear()` is called before each call to this method.
-    fn neighboors(&self, a: NodeId, into: &mut Vec<NodeId>);
+impl SearchOutcome {
+    /// Returns the path if one was found, `None` otherwise, whether the destination is
+    /// unreachable or the search gave up.
+    pub fn into_path(self) -> Option<Vec<NodeId>> {
+        match self {
+            SearchOutcome::Found(path) => Some(path),
+            SearchOutcome::Unreachable | SearchOutcome::LimitReached => None,
+        }
+    }
 }
 
-/// A wrapper around a Map, representing the graph for a four way grid type of Map, where
-/// it's possible to go north, east, south and west, but not in diagonal.
-pub struct FourWayGridGraph<'a, T: PathMap> {
-    map: &'a T,
-    width: i32,
-    height: i32,
+/// Same as [`astar_path`], but gives up after expanding `max_expansions` nodes.
+///
+/// Useful to bound the time spent searching each frame: on a huge map without a solution,
+/// [`astar_path`] would otherwise explore every reachable node before giving up.
+///
+/// Call [`SearchOutcome::into_path`] to get an `Option` like [`astar_path`]. Beware that
+/// `None` then doesn't always mean there is no path: it could also mean that the search gave
+/// up before finding it.
+///
+/// # Arguments
+///
+/// * `graph` - a struct implementing the `Graph` trait.
+/// * `from_index` - the origin.
+/// * `to_index` - the destination.
+/// * `max_expansions` - how many nodes can be expanded before the search is aborted.
+///
+/// # Panics
+///
+/// Panics if `from_index` or `to_index` are out of bounds of the graph.
+pub fn astar_path_limited<T: Graph>(
+    graph: &T,
+    from_index: NodeId,
+    to_index: NodeId,
+    max_expansions: usize,
+) -> SearchOutcome {
+    assert_index_in_bounds(graph, from_index);
+    assert_index_in_bounds(graph, to_index);
+
+    match astar_search(
+        graph,
+        from_index,
+        |index| graph.heuristic(index, to_index),
+        |index| index == to_index,
+        SearchLimits {
+            max_expansions,
+            ..SearchLimits::NONE
+        },
+    ) {
+        SearchEnd::Found(tree) => SearchOutcome::Found(tree.into_path()),
+        SearchEnd::Unreachable(_) => SearchOutcome::Unreachable,
+        SearchEnd::LimitReached => SearchOutcome::LimitReached,
+    }
+}
+
+/// Same as [`astar_path`], but using `heuristic` instead of [`Graph::heuristic`], to try
+/// another heuristic without creating a new graph type. With a heuristic always returning `0`,
+/// this is Dijkstra's algorithm.
+///
+/// # Arguments
+///
+/// * `graph` - a struct implementing the `Graph` trait.
+/// * `from_index` - the origin.
+/// * `to_index` - the destination.
+/// * `heuristic` - called with a node and the destination, returns the estimated cost between
+///   the two.
+///
+/// # Panics
+///
+/// Panics if `from_index` or `to_index` are out of bounds of the graph.
+pub fn astar_path_with<T, H>(
+    graph: &T,
+    from_index: NodeId,
+    to_index: NodeId,
+    heuristic: H,
+) -> Option<Vec<NodeId>>
+where
+    T: Graph,
+    H: Fn(NodeId, NodeId) -> f32,
+{
+    assert_index_in_bounds(graph, from_index);
+    assert_index_in_bounds(graph, to_index);
+
+    match astar_search(
+        graph,
+        from_index,
+        |index| heuristic(index, to_index),
+        |index| index == to_index,
+        SearchLimits::NONE,
+    ) {
+        SearchEnd::Found(tree) => Some(tree.into_path()),
+        SearchEnd::Unreachable(_) | SearchEnd::LimitReached => None,
+    }
+}
+
+/// Weighted A*: same as [`astar_path`], but the heuristic is multiplied by `epsilon`, making
+/// the search greedier. The higher `epsilon`, the fewer nodes are expanded, but the path can
+/// be up to `epsilon` times longer than the shortest one. With an `epsilon` of `1.0`, this is
+/// the same as [`astar_path`].
+///
+/// # Arguments
+///
+/// * `graph` - a struct implementing the `Graph` trait.
+/// * `from_index` - the origin.
+/// * `to_index` - the destination.
+/// * `epsilon` - the inflation factor of the heuristic, `1.0` or more.
+///
+/// # Panics
+///
+/// Panics if `from_index` or `to_index` are out of bounds of the graph, or if `epsilon` is
+/// smaller than `1.0`.
+pub fn astar_path_weighted<T: Graph>(
+    graph: &T,
+    from_index: NodeId,
+    to_index: NodeId,
+    epsilon: f32,
+) -> Option<Vec<NodeId>> {
+    if epsilon < 1. || epsilon.is_nan() {
+        panic!("epsilon should be at least 1.0, got {}.", epsilon);
+    }
+
+    astar_path_with(graph, from_index, to_index, |a, b| {
+        epsilon * graph.heuristic(a, b)
+    })
+}
+
+/// Same as [`astar_path`], but with several possible destinations: returns the path to the
+/// closest reachable one, or `None` if none can be reached.
+///
+/// A single search is run, with the smallest of the heuristics to each goal, which is much
+/// cheaper than searching a path to each goal.
+///
+/// # Arguments
+///
+/// * `graph` - a struct implementing the `Graph` trait.
+/// * `from_index` - the origin.
+/// * `goals` - the possible destinations.
+///
+/// # Panics
+///
+/// Panics if `from_index` or one of the goals are out of bounds of the graph.
+pub fn astar_path_to_nearest<T: Graph>(
+    graph: &T,
+    from_index: NodeId,
+    goals: &[NodeId],
+) -> Option<Vec<NodeId>> {
+    assert_index_in_bounds(graph, from_index);
+    for &goal in goals {
+        assert_index_in_bounds(graph, goal);
+    }
+    if goals.is_empty() {
+        return None;
+    }
+
+    let heuristic = |index| {
+        goals
+            .iter()
+            .map(|&goal| graph.heuristic(index, goal))
+            .fold(f32::INFINITY, f32::min)
+    };
+    match astar_search(
+        graph,
+        from_index,
+        heuristic,
+        |index| goals.contains(&index),
+        SearchLimits::NONE,
+    ) {
+        SearchEnd::Found(tree) => Some(tree.into_path()),
+        SearchEnd::Unreachable(_) | SearchEnd::LimitReached => None,
+    }
+}
+
+/// Same as [`astar_path`], but gives up on paths costing more than `max_cost`. Returns `None`
+/// if the destination can't be reached within that budget, for instance to check how far a
+/// unit can go with its remaining action points.
+///
+/// The budget applies to the accumulated cost of the path, not to the priority used by the
+/// search, which includes the heuristic.
+///
+/// # Arguments
+///
+/// * `graph` - a struct implementing the `Graph` trait.
+/// * `from_index` - the origin.
+/// * `to_index` - the destination.
+/// * `max_cost` - the maximum cost of the path.
+///
+/// # Panics
+///
+/// Panics if `from_index` or `to_index` are out of bounds of the graph.
+pub fn astar_path_budget<T: Graph>(
+    graph: &T,
+    from_index: NodeId,
+    to_index: NodeId,
+    max_cost: f32,
+) -> Option<Vec<NodeId>> {
+    assert_index_in_bounds(graph, from_index);
+    assert_index_in_bounds(graph, to_index);
+
+    match astar_search(
+        graph,
+        from_index,
+        |index| graph.heuristic(index, to_index),
+        |index| index == to_index,
+        SearchLimits {
+            max_cost,
+            ..SearchLimits::NONE
+        },
+    ) {
+        SearchEnd::Found(tree) => Some(tree.into_path()),
+        SearchEnd::Unreachable(_) | SearchEnd::LimitReached => None,
+    }
+}
+
+/// When should the A* search give up.
+struct SearchLimits {
+    /// How many nodes can be expanded.
+    max_expansions: usize,
+    /// Paths costing more than this are not explored.
+    max_cost: f32,
+}
+
+impl SearchLimits {
+    const NONE: SearchLimits = SearchLimits {
+        max_expansions: usize::MAX,
+        max_cost: f32::INFINITY,
+    };
+}
+
+/// Find the path to the closest node satisfying `is_goal`, for instance "any door". Returns
+/// `None` if no such node can be reached.
+///
+/// As there is no single destination to estimate a distance to, this is Dijkstra's algorithm:
+/// nodes are expanded in order of cost, until one of them is a goal.
+///
+/// # Arguments
+///
+/// * `graph` - a struct implementing the `Graph` trait.
+/// * `from_index` - the origin.
+/// * `is_goal` - returns `true` for the nodes that are valid destinations.
+///
+/// # Panics
+///
+/// Panics if `from_index` is out of bounds of the graph.
+pub fn astar_path_to<T, G>(graph: &T, from_index: NodeId, is_goal: G) -> Option<Vec<NodeId>>
+where
+    T: Graph,
+    G: Fn(NodeId) -> bool,
+{
+    assert_index_in_bounds(graph, from_index);
+
+    match astar_search(graph, from_index, |_| 0., is_goal, SearchLimits::NONE) {
+        SearchEnd::Found(tree) => Some(tree.into_path()),
+        SearchEnd::Unreachable(_) | SearchEnd::LimitReached => None,
+    }
+}
+
+/// Same as [`astar_path`], but when `to_index` can't be reached, returns the path to the
+/// reachable node closest to it instead, according to [`Graph::heuristic`]. Among equally close
+/// nodes, the cheapest to reach is picked. A unit chasing an unreachable target will then get
+/// as close as possible instead of standing still.
+///
+/// Only returns `None` if `from_index` is out of bounds of the graph: otherwise `from_index`
+/// itself is reachable, so a path is always found, with a single search.
+///
+/// # Arguments
+///
+/// * `graph` - a struct implementing the `Graph` trait.
+/// * `from_index` - the origin.
+/// * `to_index` - the destination.
+///
+/// # Panics
+///
+/// Panics if `to_index` is out of bounds of the graph.
+pub fn astar_path_or_closest<T: Graph>(
+    graph: &T,
+    from_index: NodeId,
+    to_index: NodeId,
+) -> Option<Vec<NodeId>> {
+    assert_index_in_bounds(graph, to_index);
+    if from_index >= graph.node_count() {
+        return None;
+    }
+
+    match astar_search(
+        graph,
+        from_index,
+        |index| graph.heuristic(index, to_index),
+        |index| index == to_index,
+        SearchLimits::NONE,
+    ) {
+        SearchEnd::Found(tree) => Some(tree.into_path()),
+        // The search explored every reachable node: walk back from the closest one.
+        SearchEnd::Unreachable(explored) => {
+            let (closest, _, cost) = explored
+                .costs
+                .iter()
+                .enumerate()
+                .filter_map(|(index, cost)| {
+                    cost.map(|cost| (index, graph.heuristic(index, to_index), cost))
+                })
+                .min_by(|(_, heuristic_a, cost_a), (_, heuristic_b, cost_b)| {
+                    heuristic_a
+                        .total_cmp(heuristic_b)
+                        .then(cost_a.total_cmp(cost_b))
+                })
+                .expect("from_index is always reachable");
+
+            Some(
+                SearchTree {
+                    from: from_index,
+                    goal: closest,
+                    cost,
+                    came_from: explored.came_from,
+                }
+                .into_path(),
+            )
+        }
+        SearchEnd::LimitReached => unreachable!("the search has no limits"),
+    }
+}
+
+/// How the A* search ended.
+enum SearchEnd {
+    Found(SearchTree),
+    Unreachable(ExploredTree),
+    LimitReached,
+}
+
+/// What's left of an A* search that ran out of nodes without finding a goal: every reachable
+/// node, with the cost of reaching it and where it was reached from.
+struct ExploredTree {
+    costs: Vec<Option<f32>>,
+    came_from: Vec<Option<NodeId>>,
+}
+
+/// What's left of a successful A* search: enough to rebuild the path.
+struct SearchTree {
+    from: NodeId,
+    goal: NodeId,
+    /// The cost of the path.
+    cost: f32,
+    came_from: Vec<Option<NodeId>>,
+}
+
+impl SearchTree {
+    fn into_path(self) -> Vec<NodeId> {
+        let mut path = vec![self.goal];
+        let mut current = self.goal;
+        while current != self.from {
+            current = self.came_from[current].unwrap();
+            path.push(current);
+        }
+        path.reverse();
+        path
+    }
+}
+
+/// The A* search shared by the `astar_path` variants: search from `from_index` until a node
+/// satisfying `is_goal` is popped, guided by `heuristic`.
+fn astar_search<T, H, G>(
+    graph: &T,
+    from_index: NodeId,
+    heuristic: H,
+    is_goal: G,
+    limits: SearchLimits,
+) -> SearchEnd
+where
+    T: Graph,
+    H: Fn(NodeId) -> f32,
+    G: Fn(NodeId) -> bool,
+{
+    let capacity = graph.node_count() / 2;
+    let mut frontier = BinaryHeap::with_capacity(capacity);
+
+    frontier.push(State {
+        cost: 0.,
+        item: from_index,
+    });
+
+    let mut came_from: Vec<Option<usize>> = vec![None; graph.node_count()];
+    let mut costs: Vec<Option<f32>> = vec![None; graph.node_count()];
+    costs[from_index] = Some(0.);
+    let mut neighboors: Vec<NodeId> = Vec::with_capacity(4);
+
+    let mut expansions = 0;
+
+    while let Some(State {
+        item: current_index,
+        ..
+    }) = frontier.pop()
+    {
+        if is_goal(current_index) {
+            return SearchEnd::Found(SearchTree {
+                from: from_index,
+                goal: current_index,
+                cost: costs[current_index].unwrap_or(0.),
+                came_from,
+            });
+        }
+
+        if expansions >= limits.max_expansions {
+            return SearchEnd::LimitReached;
+        }
+        expansions += 1;
+
+        neighboors.clear();
+        graph.neighboors(current_index, &mut neighboors);
+        for &next_index in neighboors.iter() {
+            let cost_so_far = costs[current_index].unwrap();
+            let new_cost = cost_so_far + graph.cost_between(current_index, next_index);
+            if new_cost > limits.max_cost {
+                continue;
+            }
+
+            if costs[next_index].is_none() || new_cost < costs[next_index].unwrap() {
+                let priority = new_cost + heuristic(next_index);
+                frontier.push(State {
+                    cost: priority,
+                    item: next_index,
+                });
+                came_from[next_index] = Some(current_index);
+                costs[next_index] = Some(new_cost);
+            }
+        }
+    }
+
+    SearchEnd::Unreachable(ExploredTree { costs, came_from })
+}
+
+/// Find the shortest path between `from_index` and `to_index` by running two A* searches at
+/// once, one from each end, until they meet. Returns a path as short as the one of
+/// [`astar_path`].
+///
+/// Both searches are guided by [`Graph::heuristic`], through the average of the forward and
+/// backward estimates, so that they agree on which nodes are worth expanding and can stop as
+/// soon as the best meeting point is known. The heuristic must be consistent, which is the case
+/// for the provided grid graphs.
+///
+/// The backward search walks the graph from `to_index`, which only works if the graph is
+/// symmetric: if `b` is a neighboor of `a`, `a` must be a neighboor of `b`, and
+/// `cost_between(a, b)` must equal `cost_between(b, a)`, give or take the tie breaking nudge of
+/// the provided graphs. It is the case for the provided grid graphs.
+///
+/// # Arguments
+///
+/// * `graph` - a struct implementing the `Graph` trait, with symmetric neighboors.
+/// * `from_index` - the origin.
+/// * `to_index` - the destination.
+///
+/// # Panics
+///
+/// Panics if `from_index` or `to_index` are out of bounds of the graph.
+pub fn astar_bidirectional<T: Graph>(
+    graph: &T,
+    from_index: NodeId,
+    to_index: NodeId,
+) -> Option<Vec<NodeId>> {
+    assert_index_in_bounds(graph, from_index);
+    assert_index_in_bounds(graph, to_index);
+
+    if from_index == to_index {
+        return Some(vec![from_index]);
+    }
+
+    // Average potential: the forward search uses it as is, the backward search its opposite.
+    // Their sum is zero on every node, which keeps both searches consistent with each other.
+    let potential = |index: NodeId| -> f32 {
+        (graph.heuristic(index, to_index) - graph.heuristic(from_index, index)) / 2.
+    };
+
+    let mut forward = HalfSearch::new(graph.node_count(), from_index, potential(from_index));
+    let mut backward = HalfSearch::new(graph.node_count(), to_index, -potential(to_index));
+    let mut neighboors: Vec<NodeId> = Vec::with_capacity(4);
+
+    // The cost of the best path found so far, and the node where both searches met.
+    let mut best: Option<(f32, NodeId)> = None;
+
+    while let (Some(forward_top), Some(backward_top)) = (forward.peek_key(), backward.peek_key()) {
+        if best.is_some_and(|(cost, _)| forward_top + backward_top >= cost) {
+            break;
+        }
+
+        let is_forward = forward_top <= backward_top;
+        let (search, other) = if is_forward {
+            (&mut forward, &backward)
+        } else {
+            (&mut backward, &forward)
+        };
+
+        let (current_index, current_cost) = match search.pop() {
+            Some(popped) => popped,
+            None => continue,
+        };
+
+        neighboors.clear();
+        graph.neighboors(current_index, &mut neighboors);
+        for &next_index in neighboors.iter() {
+            let step_cost = if is_forward {
+                graph.cost_between(current_index, next_index)
+            } else {
+                graph.cost_between(next_index, current_index)
+            };
+            let new_cost = current_cost + step_cost;
+            if search.costs[next_index].is_none_or(|cost| new_cost < cost) {
+                let next_potential = if is_forward {
+                    potential(next_index)
+                } else {
+                    -potential(next_index)
+                };
+                search.costs[next_index] = Some(new_cost);
+                search.came_from[next_index] = Some(current_index);
+                search.frontier.push(State {
+                    cost: new_cost + next_potential,
+                    item: (next_index, new_cost),
+                });
+            }
+
+            if let (Some(cost), Some(other_cost)) =
+                (search.costs[next_index], other.costs[next_index])
+            {
+                if best.is_none_or(|(best_cost, _)| cost + other_cost < best_cost) {
+                    best = Some((cost + other_cost, next_index));
+                }
+            }
+        }
+    }
+
+    let (_, meeting_index) = best?;
+
+    let mut path = vec![meeting_index];
+    let mut current = meeting_index;
+    while let Some(previous) = forward.came_from[current] {
+        path.push(previous);
+        current = previous;
+    }
+    path.reverse();
+
+    let mut current = meeting_index;
+    while let Some(next) = backward.came_from[current] {
+        path.push(next);
+        current = next;
+    }
+
+    Some(path)
+}
+
+/// One direction of [`astar_bidirectional`]: an A* search from a start node, where the frontier
+/// is ordered by cost plus potential.
+struct HalfSearch {
+    frontier: BinaryHeap<State<f32, (NodeId, f32)>>,
+    came_from: Vec<Option<NodeId>>,
+    costs: Vec<Option<f32>>,
+}
+
+impl HalfSearch {
+    fn new(node_count: usize, start: NodeId, start_potential: f32) -> Self {
+        let mut costs = vec![None; node_count];
+        costs[start] = Some(0.);
+        let mut frontier = BinaryHeap::new();
+        frontier.push(State {
+            cost: start_potential,
+            item: (start, 0.),
+        });
+
+        HalfSearch {
+            frontier,
+            came_from: vec![None; node_count],
+            costs,
+        }
+    }
+
+    /// The key of the best node left in the frontier, skipping stale entries.
+    fn peek_key(&mut self) -> Option<f32> {
+        while let Some(state) = self.frontier.peek() {
+            let (index, cost) = state.item;
+            if self.costs[index].is_some_and(|best| best < cost) {
+                self.frontier.pop();
+            } else {
+                return Some(state.cost);
+            }
+        }
+        None
+    }
+
+    fn pop(&mut self) -> Option<(NodeId, f32)> {
+        self.peek_key()?;
+        self.frontier.pop().map(|state| state.item)
+    }
 }
 
-impl<'a, T: PathMap> FourWayGridGraph<'a, T> {
-    pub fn new(map: &'a T) -> Self {
-        let (width, height) = map.dimensions();
-        FourWayGridGraph { map, width, height }
+/// A greedy best-first search: always expand the node that looks closest to the destination,
+/// according to [`Graph::heuristic`], ignoring the cost of the path so far.
+///
+/// Usually much faster than [`astar_path`] as it goes straight for the destination, but the
+/// returned path is **not guaranteed to be the shortest**, and can take big detours around
+/// obstacles. Good enough for monsters that don't need to be smart.
+///
+/// # Arguments
+///
+/// * `graph` - a struct implementing the `Graph` trait.
+/// * `from_index` - the origin.
+/// * `to_index` - the destination.
+///
+/// # Panics
+///
+/// Panics if `from_index` or `to_index` are out of bounds of the graph.
+pub fn greedy_best_first<T: Graph>(
+    graph: &T,
+    from_index: NodeId,
+    to_index: NodeId,
+) -> Option<Vec<NodeId>> {
+    assert_index_in_bounds(graph, from_index);
+    assert_index_in_bounds(graph, to_index);
+
+    let mut frontier = BinaryHeap::new();
+    let mut came_from: Vec<Option<NodeId>> = vec![None; graph.node_count()];
+    let mut visited = vec![false; graph.node_count()];
+    let mut neighboors: Vec<NodeId> = Vec::with_capacity(4);
+
+    visited[from_index] = true;
+    frontier.push(State {
+        cost: graph.heuristic(from_index, to_index),
+        item: from_index,
+    });
+
+    while let Some(State {
+        item: current_index,
+        ..
+    }) = frontier.pop()
+    {
+        if current_index == to_index {
+            return reconstruct_path(from_index, to_index, came_from, 0.);
+        }
+
+        neighboors.clear();
+        graph.neighboors(current_index, &mut neighboors);
+        for &next_index in neighboors.iter() {
+            if !visited[next_index] {
+                visited[next_index] = true;
+                came_from[next_index] = Some(current_index);
+                frontier.push(State {
+                    cost: graph.heuristic(next_index, to_index),
+                    item: next_index,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+/// Check if there is a path between `from_index` and `to_index`, without building it.
+///
+/// Cheaper than `astar_path(...).is_some()`: as only the existence of a path matters, the
+/// search goes straight for the destination like [`greedy_best_first`], and doesn't keep track
+/// of costs nor of the way back.
+///
+/// # Arguments
+///
+/// * `graph` - a struct implementing the `Graph` trait.
+/// * `from_index` - the origin.
+/// * `to_index` - the destination.
+///
+/// # Panics
+///
+/// Panics if `from_index` or `to_index` are out of bounds of the graph.
+pub fn is_reachable<T: Graph>(graph: &T, from_index: NodeId, to_index: NodeId) -> bool {
+    assert_index_in_bounds(graph, from_index);
+    assert_index_in_bounds(graph, to_index);
+
+    let mut frontier = BinaryHeap::new();
+    let mut visited = vec![false; graph.node_count()];
+    let mut neighboors: Vec<NodeId> = Vec::with_capacity(4);
+
+    visited[from_index] = true;
+    frontier.push(State {
+        cost: graph.heuristic(from_index, to_index),
+        item: from_index,
+    });
+
+    while let Some(State {
+        item: current_index,
+        ..
+    }) = frontier.pop()
+    {
+        if current_index == to_index {
+            return true;
+        }
+
+        neighboors.clear();
+        graph.neighboors(current_index, &mut neighboors);
+        for &next_index in neighboors.iter() {
+            if !visited[next_index] {
+                visited[next_index] = true;
+                frontier.push(State {
+                    cost: graph.heuristic(next_index, to_index),
+                    item: next_index,
+                });
+            }
+        }
+    }
+
+    false
+}
+
+/// Same as [`astar_path`], but writes the path into `out` instead of allocating a new vector.
+/// `out` is cleared first, and stays empty if no path is found. Returns whether a path was
+/// found.
+///
+/// The search itself still allocates its buffers: to run many searches without any
+/// allocation, use [`Pathfinder::find_into`].
+///
+/// # Arguments
+///
+/// * `graph` - a struct implementing the `Graph` trait.
+/// * `from_index` - the origin.
+/// * `to_index` - the destination.
+/// * `out` - where to write the path, origin first.
+///
+/// # Panics
+///
+/// Panics if `from_index` or `to_index` are out of bounds of the graph.
+pub fn astar_path_into<T: Graph>(
+    graph: &T,
+    from_index: NodeId,
+    to_index: NodeId,
+    out: &mut Vec<NodeId>,
+) -> bool {
+    Pathfinder::new(graph.node_count()).find_into(graph, from_index, to_index, out)
+}
+
+/// Reusable buffers for A*, to run many searches without allocating each time.
+///
+/// Gives the same paths as [`astar_path`]. Between two searches, the buffers are not cleared:
+/// each entry is stamped with the search that wrote it, so starting a new search only costs as
+/// much as the nodes it touches.
+///
+/// # Examples
+/// ```
+/// use torchbearer::{
+///     map::{grid_map, CellMeaning},
+///     path::{FourWayGridGraph, Pathfinder},
+/// };
+///
+/// let map = grid_map(4, 4, vec![true; 16], CellMeaning::Open);
+/// let graph = FourWayGridGraph::new(&map);
+/// let mut pathfinder = Pathfinder::new(16);
+///
+/// for to in 1..16 {
+///     assert!(pathfinder.find(&graph, 0, to).is_some());
+/// }
+/// ```
+pub struct Pathfinder {
+    came_from: Vec<NodeId>,
+    costs: Vec<f32>,
+    /// The generation of the search that last wrote the entry.
+    stamps: Vec<u32>,
+    generation: u32,
+    frontier: BinaryHeap<State<f32, NodeId>>,
+    neighboors: Vec<NodeId>,
+}
+
+impl Pathfinder {
+    /// Creates a pathfinder for graphs of `node_count` nodes. It grows if used with a bigger
+    /// graph.
+    pub fn new(node_count: usize) -> Self {
+        Pathfinder {
+            came_from: vec![0; node_count],
+            costs: vec![0.; node_count],
+            stamps: vec![0; node_count],
+            generation: 0,
+            frontier: BinaryHeap::with_capacity(node_count / 2),
+            neighboors: Vec::with_capacity(4),
+        }
+    }
+
+    /// Same as [`astar_path`], reusing the buffers of the pathfinder.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from_index` or `to_index` are out of bounds of the graph.
+    pub fn find<T: Graph>(
+        &mut self,
+        graph: &T,
+        from_index: NodeId,
+        to_index: NodeId,
+    ) -> Option<Vec<NodeId>> {
+        let mut path = vec![];
+        if self.find_into(graph, from_index, to_index, &mut path) {
+            Some(path)
+        } else {
+            None
+        }
+    }
+
+    /// Same as [`astar_path_into`], reusing the buffers of the pathfinder. Once the buffers
+    /// and `out` are big enough, searching doesn't allocate anymore.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from_index` or `to_index` are out of bounds of the graph.
+    pub fn find_into<T: Graph>(
+        &mut self,
+        graph: &T,
+        from_index: NodeId,
+        to_index: NodeId,
+        out: &mut Vec<NodeId>,
+    ) -> bool {
+        out.clear();
+        assert_index_in_bounds(graph, from_index);
+        assert_index_in_bounds(graph, to_index);
+
+        self.start_search(graph.node_count());
+        let generation = self.generation;
+
+        self.frontier.push(State {
+            cost: 0.,
+            item: from_index,
+        });
+        self.stamps[from_index] = generation;
+        self.costs[from_index] = 0.;
+        self.came_from[from_index] = from_index;
+
+        let mut found = false;
+        while let Some(State {
+            item: current_index,
+            ..
+        }) = self.frontier.pop()
+        {
+            if current_index == to_index {
+                found = true;
+                break;
+            }
+
+            self.neighboors.clear();
+            graph.neighboors(current_index, &mut self.neighboors);
+            for &next_index in self.neighboors.iter() {
+                let new_cost =
+                    self.costs[current_index] + graph.cost_between(current_index, next_index);
+
+                if self.stamps[next_index] != generation || new_cost < self.costs[next_index] {
+                    let priority = new_cost + graph.heuristic(next_index, to_index);
+                    self.frontier.push(State {
+                        cost: priority,
+                        item: next_index,
+                    });
+                    self.stamps[next_index] = generation;
+                    self.came_from[next_index] = current_index;
+                    self.costs[next_index] = new_cost;
+                }
+            }
+        }
+
+        if !found {
+            return false;
+        }
+
+        out.push(to_index);
+        let mut current = to_index;
+        while current != from_index {
+            current = self.came_from[current];
+            out.push(current);
+        }
+        out.reverse();
+        true
+    }
+
+    /// Get the buffers ready for a new search.
+    fn start_search(&mut self, node_count: usize) {
+        if self.stamps.len() < node_count {
+            self.came_from.resize(node_count, 0);
+            self.costs.resize(node_count, 0.);
+            self.stamps.resize(node_count, 0);
+        }
+        self.frontier.clear();
+
+        self.generation = self.generation.wrapping_add(1);
+        if self.generation == 0 {
+            // Stamps could be mistaken for the current generation, actually clear them.
+            self.stamps.iter_mut().for_each(|stamp| *stamp = 0);
+            self.generation = 1;
+        }
+    }
+}
+
+/// A Jump Point Search implementation for an eight way grid where every step has the same
+/// cost: `1` for cardinal moves, `√2` for diagonal moves. Returns an optional vector containing
+/// all the points to walk through, including the origin and destination.
+///
+/// Finds a path as short as [`astar_path`] on an [`EightWayGridGraph`] with
+/// [`CornerPolicy::RequireBoth`], but by jumping along straight lines it only needs to expand
+/// a handful of nodes, which makes it much faster on big open maps. Diagonal moves never cut
+/// corners. The [`PathMap::cost`] of the tiles is ignored.
+///
+/// See <https://harablog.wordpress.com/2011/09/07/jump-point-search/> for the original idea.
+///
+/// # Arguments
+///
+/// * `map` - a struct implementing the `PathMap` trait.
+/// * `from` - the origin.
+/// * `to` - the destination.
+///
+/// # Panics
+///
+/// Panics if `from` or `to` are out of bounds of the map.
+pub fn jps_path<T: PathMap>(map: &T, from: Point, to: Point) -> Option<Vec<Point>> {
+    assert_point_in_bounds(map, from);
+    assert_point_in_bounds(map, to);
+
+    let (width, height) = map.dimensions();
+    let index = |(x, y): Point| (x + y * width) as usize;
+    let node_count = (width * height) as usize;
+
+    let mut frontier = BinaryHeap::new();
+    let mut came_from: Vec<Option<Point>> = vec![None; node_count];
+    let mut costs: Vec<Option<f32>> = vec![None; node_count];
+    let mut closed = vec![false; node_count];
+    let mut successors: Vec<Point> = Vec::with_capacity(8);
+
+    costs[index(from)] = Some(0.);
+    frontier.push(State {
+        cost: 0.,
+        item: from,
+    });
+
+    while let Some(State { item: current, .. }) = frontier.pop() {
+        if current == to {
+            return Some(expand_jump_points(from, to, &came_from, index));
+        }
+        if closed[index(current)] {
+            continue;
+        }
+        closed[index(current)] = true;
+
+        successors.clear();
+        jps_neighboors(map, current, came_from[index(current)], &mut successors);
+        for &neighboor in successors.iter() {
+            let direction = (
+                (neighboor.0 - current.0).signum(),
+                (neighboor.1 - current.1).signum(),
+            );
+            let jump_point = match jump(map, neighboor, direction, to) {
+                Some(jump_point) => jump_point,
+                None => continue,
+            };
+            if closed[index(jump_point)] {
+                continue;
+            }
+
+            let new_cost = costs[index(current)].unwrap() + octile_distance(current, jump_point);
+            if costs[index(jump_point)].is_none_or(|cost| new_cost < cost) {
+                costs[index(jump_point)] = Some(new_cost);
+                came_from[index(jump_point)] = Some(current);
+                frontier.push(State {
+                    cost: new_cost + octile_distance(jump_point, to),
+                    item: jump_point,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+fn octile_distance((xa, ya): Point, (xb, yb): Point) -> f32 {
+    let (dx, dy) = ((xa - xb).abs() as f32, (ya - yb).abs() as f32);
+    dx.max(dy) + (std::f32::consts::SQRT_2 - 1.) * dx.min(dy)
+}
+
+/// Is the position in the map, and walkable.
+fn is_walkable_in_bounds<T: PathMap>(map: &T, (x, y): Point) -> bool {
+    let (width, height) = map.dimensions();
+    x >= 0 && y >= 0 && x < width && y < height && map.is_walkable((x, y))
+}
+
+/// The neighboors worth jumping toward from `(x, y)`, depending on the direction we came from.
+fn jps_neighboors<T: PathMap>(
+    map: &T,
+    (x, y): Point,
+    parent: Option<Point>,
+    into: &mut Vec<Point>,
+) {
+    let walkable = |position: Point| is_walkable_in_bounds(map, position);
+
+    let (px, py) = match parent {
+        Some(parent) => parent,
+        None => {
+            for (dx, dy) in [(0, 1), (0, -1), (-1, 0), (1, 0)] {
+                if walkable((x + dx, y + dy)) {
+                    into.push((x + dx, y + dy));
+                }
+            }
+            for (dx, dy) in [(1, 1), (1, -1), (-1, 1), (-1, -1)] {
+                if walkable((x + dx, y + dy)) && walkable((x + dx, y)) && walkable((x, y + dy)) {
+                    into.push((x + dx, y + dy));
+                }
+            }
+            return;
+        }
+    };
+
+    let (dx, dy) = ((x - px).signum(), (y - py).signum());
+    if dx != 0 && dy != 0 {
+        let next_x = walkable((x + dx, y));
+        let next_y = walkable((x, y + dy));
+        if next_y {
+            into.push((x, y + dy));
+        }
+        if next_x {
+            into.push((x + dx, y));
+        }
+        if next_x && next_y && walkable((x + dx, y + dy)) {
+            into.push((x + dx, y + dy));
+        }
+    } else if dx != 0 {
+        let next = walkable((x + dx, y));
+        let top = walkable((x, y - 1));
+        let bottom = walkable((x, y + 1));
+        if next {
+            into.push((x + dx, y));
+            if top && walkable((x + dx, y - 1)) {
+                into.push((x + dx, y - 1));
+            }
+            if bottom && walkable((x + dx, y + 1)) {
+                into.push((x + dx, y + 1));
+            }
+        }
+        if top {
+            into.push((x, y - 1));
+        }
+        if bottom {
+            into.push((x, y + 1));
+        }
+    } else {
+        let next = walkable((x, y + dy));
+        let left = walkable((x - 1, y));
+        let right = walkable((x + 1, y));
+        if next {
+            into.push((x, y + dy));
+            if left && walkable((x - 1, y + dy)) {
+                into.push((x - 1, y + dy));
+            }
+            if right && walkable((x + 1, y + dy)) {
+                into.push((x + 1, y + dy));
+            }
+        }
+        if left {
+            into.push((x - 1, y));
+        }
+        if right {
+            into.push((x + 1, y));
+        }
+    }
+}
+
+/// Go in `direction` from `(x, y)` until reaching a jump point: the destination, or a position
+/// with a forced neighboor. Returns `None` when hitting a wall first.
+fn jump<T: PathMap>(map: &T, (x, y): Point, (dx, dy): Point, to: Point) -> Option<Point> {
+    let walkable = |position: Point| is_walkable_in_bounds(map, position);
+    let (mut x, mut y) = (x, y);
+
+    loop {
+        if !walkable((x, y)) {
+            return None;
+        }
+        if (x, y) == to {
+            return Some((x, y));
+        }
+
+        if dx != 0 && dy != 0 {
+            if jump(map, (x + dx, y), (dx, 0), to).is_some()
+                || jump(map, (x, y + dy), (0, dy), to).is_some()
+            {
+                return Some((x, y));
+            }
+            // No corner cutting.
+            if !walkable((x + dx, y)) || !walkable((x, y + dy)) {
+                return None;
+            }
+        } else if dx != 0 {
+            if (walkable((x, y - 1)) && !walkable((x - dx, y - 1)))
+                || (walkable((x, y + 1)) && !walkable((x - dx, y + 1)))
+            {
+                return Some((x, y));
+            }
+        } else if (walkable((x - 1, y)) && !walkable((x - 1, y - dy)))
+            || (walkable((x + 1, y)) && !walkable((x + 1, y - dy)))
+        {
+            return Some((x, y));
+        }
+
+        x += dx;
+        y += dy;
+    }
+}
+
+/// Rebuild the full path, tile by tile, from the jump points.
+fn expand_jump_points<F: Fn(Point) -> usize>(
+    from: Point,
+    to: Point,
+    came_from: &[Option<Point>],
+    index: F,
+) -> Vec<Point> {
+    let mut jump_points = vec![to];
+    let mut current = to;
+    while current != from {
+        current = came_from[index(current)].unwrap();
+        jump_points.push(current);
+    }
+    jump_points.reverse();
+
+    let mut path = vec![from];
+    for segment in jump_points.windows(2) {
+        let (mut x, mut y) = segment[0];
+        let (dx, dy) = ((segment[1].0 - x).signum(), (segment[1].1 - y).signum());
+        while (x, y) != segment[1] {
+            x += dx;
+            y += dy;
+            path.push((x, y));
+        }
+    }
+    path
+}
+
+/// Theta*, an any-angle variant of A*: the path is not limited to the eight directions of the
+/// grid, and can contain long straight segments between points far from each other. Returns
+/// an optional vector containing the waypoints, including the origin and destination.
+///
+/// The search moves like on an [`EightWayGridGraph`], but each time a node is reached, it tries
+/// to link it directly to the parent of the current node: if a [`BresenhamLine`] between the
+/// two only crosses walkable tiles, the intermediate node is skipped. Costs are euclidean
+/// distances. The [`PathMap::cost`] of the tiles is ignored.
+///
+/// # Arguments
+///
+/// * `map` - a struct implementing the `PathMap` trait.
+/// * `from` - the origin.
+/// * `to` - the destination.
+///
+/// # Panics
+///
+/// Panics if `from` or `to` are out of bounds of the map.
+pub fn theta_star<T: PathMap>(map: &T, from: Point, to: Point) -> Option<Vec<Point>> {
+    assert_point_in_bounds(map, from);
+    assert_point_in_bounds(map, to);
+
+    let graph = EightWayGridGraph::new(map);
+    let from_index = graph.point_to_index(from);
+    let to_index = graph.point_to_index(to);
+
+    let mut frontier = BinaryHeap::new();
+    let mut parents: Vec<Option<NodeId>> = vec![None; graph.node_count()];
+    let mut costs: Vec<Option<f32>> = vec![None; graph.node_count()];
+    let mut closed = vec![false; graph.node_count()];
+    let mut neighboors: Vec<NodeId> = Vec::with_capacity(8);
+
+    parents[from_index] = Some(from_index);
+    costs[from_index] = Some(0.);
+    frontier.push(State {
+        cost: 0.,
+        item: from_index,
+    });
+
+    let in_sight = |a: Point, b: Point| BresenhamLine::new(a, b).all(|p| map.is_walkable(p));
+
+    while let Some(State {
+        item: current_index,
+        ..
+    }) = frontier.pop()
+    {
+        if current_index == to_index {
+            let mut path = vec![to];
+            let mut current = to_index;
+            while current != from_index {
+                current = parents[current].unwrap();
+                path.push(graph.index_to_point(current));
+            }
+            path.reverse();
+            return Some(path);
+        }
+        if closed[current_index] {
+            continue;
+        }
+        closed[current_index] = true;
+
+        let current = graph.index_to_point(current_index);
+        let parent_index = parents[current_index].unwrap();
+        let parent = graph.index_to_point(parent_index);
+
+        neighboors.clear();
+        graph.neighboors(current_index, &mut neighboors);
+        for &next_index in neighboors.iter() {
+            if closed[next_index] {
+                continue;
+            }
+            let next = graph.index_to_point(next_index);
+
+            let (new_parent_index, new_cost) = if in_sight(parent, next) {
+                (
+                    parent_index,
+                    costs[parent_index].unwrap() + euclidean_distance(parent, next),
+                )
+            } else {
+                (
+                    current_index,
+                    costs[current_index].unwrap() + euclidean_distance(current, next),
+                )
+            };
+
+            if costs[next_index].is_none_or(|cost| new_cost < cost) {
+                costs[next_index] = Some(new_cost);
+                parents[next_index] = Some(new_parent_index);
+                frontier.push(State {
+                    cost: new_cost + euclidean_distance(next, to),
+                    item: next_index,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+fn euclidean_distance((xa, ya): Point, (xb, yb): Point) -> f32 {
+    let (dx, dy) = ((xa - xb) as f32, (ya - yb) as f32);
+    (dx * dx + dy * dy).sqrt()
+}
+
+/// Costs closer than this are considered equal when looking for all shortest paths.
+const COST_EPSILON: f32 = 1e-4;
+
+/// An A* pathfinding implementation returning every shortest path between two nodes, instead
+/// of an arbitrary one. Each path contains the origin and the destination.
+///
+/// The number of shortest paths can grow combinatorially (think of an open field), so at most
+/// `limit` paths are returned.
+///
+/// Note that [`FourWayGridGraph`] nudges its costs to break ties, so on that graph paths that
+/// look equivalent will rarely cost exactly the same. This is best used with graphs returning
+/// uniform costs.
+///
+/// # Arguments
+///
+/// * `graph` - a struct implementing the `Graph` trait.
+/// * `from_index` - the origin.
+/// * `to_index` - the destination.
+/// * `limit` - the maximum amount of paths returned.
+///
+/// # Panics
+///
+/// Panics if `from_index` or `to_index` are out of bounds. (Meaning, a bigger index that the total node count of the graph).
+pub fn astar_all_shortest_paths<T: Graph>(
+    graph: &T,
+    from_index: NodeId,
+    to_index: NodeId,
+    limit: usize,
+) -> Vec<Vec<NodeId>> {
+    assert_index_in_bounds(graph, from_index);
+    assert_index_in_bounds(graph, to_index);
+
+    if limit == 0 {
+        return vec![];
+    }
+
+    let mut frontier = BinaryHeap::with_capacity(graph.node_count() / 2);
+    frontier.push(State {
+        cost: 0.,
+        item: from_index,
+    });
+
+    let mut came_from: Vec<Vec<NodeId>> = vec![vec![]; graph.node_count()];
+    let mut costs: Vec<Option<f32>> = vec![None; graph.node_count()];
+    let mut closed = vec![false; graph.node_count()];
+    costs[from_index] = Some(0.);
+    let mut neighboors: Vec<NodeId> = Vec::with_capacity(4);
+
+    while let Some(State {
+        item: current_index,
+        cost: priority,
+    }) = frontier.pop()
+    {
+        if let Some(to_cost) = costs[to_index] {
+            // Every node left in the frontier leads to a more expensive path.
+            if closed[to_index] && priority > to_cost + COST_EPSILON {
+                break;
+            }
+        }
+        if closed[current_index] {
+            continue;
+        }
+        closed[current_index] = true;
+
+        if current_index == to_index {
+            continue;
+        }
+
+        neighboors.clear();
+        graph.neighboors(current_index, &mut neighboors);
+        let cost_so_far = costs[current_index].unwrap();
+        for &next_index in neighboors.iter() {
+            let new_cost = cost_so_far + graph.cost_between(current_index, next_index);
+
+            match costs[next_index] {
+                Some(cost) if (new_cost - cost).abs() <= COST_EPSILON => {
+                    if !came_from[next_index].contains(&current_index) {
+                        came_from[next_index].push(current_index);
+                    }
+                }
+                Some(cost) if new_cost > cost => {}
+                _ => {
+                    let priority = new_cost + graph.heuristic(next_index, to_index);
+                    frontier.push(State {
+                        cost: priority,
+                        item: next_index,
+                    });
+                    came_from[next_index].clear();
+                    came_from[next_index].push(current_index);
+                    costs[next_index] = Some(new_cost);
+                }
+            }
+        }
+    }
+
+    if costs[to_index].is_none() {
+        return vec![];
+    }
+
+    // Walk back from the destination, following every predecessor.
+    let mut paths = vec![];
+    let mut stack = vec![vec![to_index]];
+    while let Some(partial) = stack.pop() {
+        let last = partial[partial.len() - 1];
+        if last == from_index {
+            let mut path = partial;
+            path.reverse();
+            paths.push(path);
+            if paths.len() >= limit {
+                break;
+            }
+            continue;
+        }
+
+        for &previous in came_from[last].iter().rev() {
+            let mut next = partial.clone();
+            next.push(previous);
+            stack.push(next);
+        }
+    }
+
+    paths
+}
+
+/// Compute a Dijkstra map: for every node of the graph, the cost to reach the closest goal.
+///
+/// All the goals start at a cost of `0`, and costs are then relaxed outward. Chasing the closest
+/// goal is a matter of stepping to the neighboor with the lowest cost. The neighboors of the
+/// graph are expected to be symmetric: if `b` is a neighboor of `a`, `a` is a neighboor of `b`.
+///
+/// # Arguments
+///
+/// * `graph` - a struct implementing the `Graph` trait.
+/// * `goals` - the nodes to reach.
+///
+/// # Panics
+///
+/// Panics if one of the goals is out of bounds of the graph.
+///
+/// # Examples
+/// ```
+/// use torchbearer::{
+///     map::{grid_map, CellMeaning},
+///     path::{dijkstra_map, FourWayGridGraph},
+/// };
+///
+/// let map = grid_map(3, 1, vec![true, true, false], CellMeaning::Open);
+/// let graph = FourWayGridGraph::new(&map);
+///
+/// let costs = dijkstra_map(&graph, &[0]);
+///
+/// assert_eq!(costs[0], Some(0.));
+/// assert!(costs[1].is_some());
+/// assert_eq!(costs[2], None);
+/// ```
+pub fn dijkstra_map<T: Graph>(graph: &T, goals: &[NodeId]) -> Vec<Option<f32>> {
+    for &goal in goals {
+        assert_index_in_bounds(graph, goal);
+    }
+
+    let mut neighboors: Vec<NodeId> = Vec::with_capacity(4);
+    bounded_dijkstra(
+        graph.node_count(),
+        goals,
+        f32::INFINITY,
+        |current, steps| {
+            neighboors.clear();
+            graph.neighboors(current, &mut neighboors);
+            // Walking from the neighboor toward the goal.
+            steps.extend(
+                neighboors
+                    .iter()
+                    .map(|&next| (next, graph.cost_between(next, current))),
+            );
+        },
+    )
+}
+
+/// Cost bounded Dijkstra expansion shared by [`dijkstra_map`], [`reachable`] and
+/// [`targetable_tiles`]: starting at a cost of `0` from each of `starts`, relax the costs outward
+/// as long as they stay within `max_cost`. `expand` pushes each neighboor of a node into
+/// `steps`, with the cost of the step between them.
+///
+/// Returns, for each node, the cost of the cheapest path to it, or `None` if out of reach.
+fn bounded_dijkstra<E>(
+    node_count: usize,
+    starts: &[NodeId],
+    max_cost: f32,
+    mut expand: E,
+) -> Vec<Option<f32>>
+where
+    E: FnMut(NodeId, &mut Vec<(NodeId, f32)>),
+{
+    let mut costs: Vec<Option<f32>> = vec![None; node_count];
+    let mut frontier = BinaryHeap::with_capacity(node_count / 2);
+    let mut steps: Vec<(NodeId, f32)> = Vec::with_capacity(4);
+
+    for &start in starts {
+        costs[start] = Some(0.);
+        frontier.push(State {
+            cost: 0.,
+            item: start,
+        });
+    }
+
+    while let Some(State {
+        item: current_index,
+        cost: current_cost,
+    }) = frontier.pop()
+    {
+        if costs[current_index].is_some_and(|cost| cost < current_cost) {
+            // Stale entry, a cheaper way was found in the meantime.
+            continue;
+        }
+
+        steps.clear();
+        expand(current_index, &mut steps);
+        for &(next_index, step_cost) in steps.iter() {
+            let new_cost = current_cost + step_cost;
+            if new_cost <= max_cost && costs[next_index].is_none_or(|cost| new_cost < cost) {
+                costs[next_index] = Some(new_cost);
+                frontier.push(State {
+                    cost: new_cost,
+                    item: next_index,
+                });
+            }
+        }
+    }
+
+    costs
+}
+
+/// Find every node that can be reached from `from` with a path costing at most `max_cost`,
+/// for instance to highlight the movement range of a unit.
+///
+/// Returns each reachable node once, with the cost of the cheapest path to it, ordered by
+/// `NodeId`. `from` itself is included, with a cost of `0`.
+///
+/// # Arguments
+///
+/// * `graph` - a struct implementing the `Graph` trait.
+/// * `from_index` - the origin.
+/// * `max_cost` - the maximum cost of the paths.
+///
+/// # Panics
+///
+/// Panics if `from_index` is out of bounds of the graph.
+pub fn reachable<T: Graph>(graph: &T, from_index: NodeId, max_cost: f32) -> Vec<(NodeId, f32)> {
+    assert_index_in_bounds(graph, from_index);
+
+    let mut neighboors: Vec<NodeId> = Vec::with_capacity(4);
+    let costs = bounded_dijkstra(
+        graph.node_count(),
+        &[from_index],
+        max_cost,
+        |current, steps| {
+            neighboors.clear();
+            graph.neighboors(current, &mut neighboors);
+            steps.extend(
+                neighboors
+                    .iter()
+                    .map(|&next| (next, graph.cost_between(current, next))),
+            );
+        },
+    );
+
+    costs
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, cost)| cost.map(|cost| (index, cost)))
+        .collect()
+}
+
+/// Count the steps from `from_index` to every node of the graph, ignoring the cost of each
+/// step. Cheaper than [`dijkstra_map`] when only the number of moves matters, for instance for
+/// "within 5 tiles" checks.
+///
+/// Returns a vector indexed by `NodeId`, with `Some(0)` for the origin and `None` for
+/// unreachable nodes.
+///
+/// # Arguments
+///
+/// * `graph` - a struct implementing the `Graph` trait.
+/// * `from_index` - the origin.
+///
+/// # Panics
+///
+/// Panics if `from_index` is out of bounds of the graph.
+pub fn breadth_first<T: Graph>(graph: &T, from_index: NodeId) -> Vec<Option<u32>> {
+    assert_index_in_bounds(graph, from_index);
+
+    let mut steps: Vec<Option<u32>> = vec![None; graph.node_count()];
+    let mut queue = VecDeque::from([from_index]);
+    let mut neighboors: Vec<NodeId> = Vec::with_capacity(4);
+    steps[from_index] = Some(0);
+
+    while let Some(current_index) = queue.pop_front() {
+        let next_steps = steps[current_index].map(|step| step + 1);
+
+        neighboors.clear();
+        graph.neighboors(current_index, &mut neighboors);
+        for &next_index in neighboors.iter() {
+            if steps[next_index].is_none() {
+                steps[next_index] = next_steps;
+                queue.push_back(next_index);
+            }
+        }
+    }
+
+    steps
+}
+
+/// Spread the influence of several sources over the graph, for instance the danger around
+/// enemies or the interest around loot, to score positions in a tactical AI.
+///
+/// Each source starts with its value, multiplied by `decay` for each step away from it, steps
+/// being counted like in [`breadth_first`]. The contributions of all the sources are summed up.
+/// Returns a vector indexed by `NodeId`, with `0.` for the nodes no source can reach.
+///
+/// # Arguments
+///
+/// * `graph` - a struct implementing the `Graph` trait.
+/// * `sources` - the nodes spreading influence, with their value. Use negative values for
+///   repulsive sources.
+/// * `decay` - the factor applied at each step, usually between `0.` and `1.`.
+///
+/// # Panics
+///
+/// Panics if one of the sources is out of bounds of the graph.
+///
+/// # Examples
+/// ```
+/// use torchbearer::{
+///     map::Grid,
+///     path::{influence_map, FourWayGridGraph},
+/// };
+///
+/// let map = Grid::new(5, 1);
+/// let graph = FourWayGridGraph::new(&map);
+///
+/// let influence = influence_map(&graph, &[(0, 8.), (4, 2.)], 0.5);
+///
+/// assert_eq!(influence, [8.125, 4.25, 2.5, 2., 2.5]);
+/// ```
+pub fn influence_map<T: Graph>(graph: &T, sources: &[(NodeId, f32)], decay: f32) -> Vec<f32> {
+    let mut influence = vec![0.; graph.node_count()];
+    for &(source, value) in sources {
+        for (total, steps) in influence.iter_mut().zip(breadth_first(graph, source)) {
+            if let Some(steps) = steps {
+                *total += value * decay.powi(steps as i32);
+            }
+        }
+    }
+    influence
+}
+
+/// Follow a Dijkstra map downhill from `from`, then straighten the result using line of sight.
+///
+/// Descending a Dijkstra map (a vector holding, for each tile, the cost to the closest goal)
+/// produces staircase like movements. The descent here steps, four way, to the neighboor with
+/// the lowest cost until no neighboor is lower, then drops every intermediate waypoint that can
+/// be skipped with a straight [`BresenhamLine`] crossing only walkable tiles.
+///
+/// The returned path contains `from` and the final position, usually a goal.
+///
+/// # Arguments
+///
+/// * `dijkstra` - the cost of each tile to the closest goal, indexed by `x + y * width`.
+///   `None` for unreachable tiles.
+/// * `map` - a struct implementing the `PathMap` trait.
+/// * `from` - the origin.
+///
+/// # Panics
+///
+/// Panics if `from` is out of bounds of the map, or if `dijkstra` doesn't contain a value for
+/// each tile of the map.
+pub fn descend_path_smoothed<T: PathMap>(
+    dijkstra: &[Option<f32>],
+    map: &T,
+    from: Point,
+) -> Vec<Point> {
+    let path = descend_dijkstra(dijkstra, map, from);
+    smooth_path(map, &path)
+}
+
+/// Step by step descent of a Dijkstra map, four way.
+fn descend_dijkstra<T: PathMap>(dijkstra: &[Option<f32>], map: &T, from: Point) -> Vec<Point> {
+    assert_point_in_bounds(map, from);
+    let (width, height) = map.dimensions();
+    if dijkstra.len() != (width * height) as usize {
+        panic!(
+            "The dijkstra map should contain {} elements for a map of ({}, {}), got {}.",
+            width * height,
+            width,
+            height,
+            dijkstra.len()
+        );
+    }
+
+    let mut path = vec![from];
+    let (mut x, mut y) = from;
+    let mut current = match dijkstra[(x + y * width) as usize] {
+        Some(cost) => cost,
+        None => return path,
+    };
+
+    loop {
+        let mut best: Option<(Point, f32)> = None;
+        for (nx, ny) in [(x, y + 1), (x, y - 1), (x - 1, y), (x + 1, y)] {
+            if nx < 0 || ny < 0 || nx >= width || ny >= height || !map.is_walkable((nx, ny)) {
+                continue;
+            }
+            if let Some(cost) = dijkstra[(nx + ny * width) as usize] {
+                if cost < best.map_or(current, |(_, best_cost)| best_cost) {
+                    best = Some(((nx, ny), cost));
+                }
+            }
+        }
+
+        match best {
+            Some((position, cost)) => {
+                path.push(position);
+                (x, y) = position;
+                current = cost;
+            }
+            None => return path,
+        }
+    }
+}
+
+/// Straighten a path by dropping the waypoints that can be skipped: walking the path, a
+/// waypoint is dropped if a [`BresenhamLine`] from the last kept waypoint to the next one only
+/// crosses walkable tiles.
+///
+/// Turns the staircases of a four way path into diagonal looking lines. The first and last
+/// points are always kept, and the lines between consecutive waypoints never cross a wall.
+///
+/// # Arguments
+///
+/// * `map` - a struct implementing the `PathMap` trait.
+/// * `path` - the path to straighten, for instance the result of [`astar_path_fourwaygrid`].
+pub fn smooth_path<T: PathMap>(map: &T, path: &[Point]) -> Vec<Point> {
+    if path.len() < 3 {
+        return path.to_vec();
+    }
+
+    let mut smoothed = vec![path[0]];
+    let mut anchor = path[0];
+    for window in path.windows(2).skip(1) {
+        let (current, next) = (window[0], window[1]);
+        if !BresenhamLine::new(anchor, next).all(|position| map.is_walkable(position)) {
+            smoothed.push(current);
+            anchor = current;
+        }
+    }
+    smoothed.push(path[path.len() - 1]);
+
+    smoothed
+}
+
+/// Interpolate a smooth Catmull-Rom spline going through the center of each tile of a path,
+/// for instance to have a camera follow a path without jerky moves.
+///
+/// The center of the tile `(x, y)` is `(x + 0.5, y + 0.5)`. Each segment between two
+/// consecutive points is sampled `samples_per_segment` times, starting with the segment's first
+/// point, and the last point of the path is added at the end: for `n` points, the result
+/// contains `(n - 1) * samples_per_segment + 1` samples. At both ends of the path, the missing
+/// control points are taken as the end points themselves.
+///
+/// # Arguments
+///
+/// * `points` - The path to follow.
+/// * `samples_per_segment` - How many samples between two consecutive points.
+///
+/// # Panics
+///
+/// Panics if `samples_per_segment` is `0`.
+pub fn catmull_rom_path(points: &[Point], samples_per_segment: usize) -> Vec<(f32, f32)> {
+    if samples_per_segment == 0 {
+        panic!("samples_per_segment should be at least 1, got 0.");
+    }
+
+    let centers: Vec<(f32, f32)> = points
+        .iter()
+        .map(|&(x, y)| (x as f32 + 0.5, y as f32 + 0.5))
+        .collect();
+    let last = match centers.last() {
+        Some(&last) => last,
+        None => return vec![],
+    };
+
+    let mut samples = Vec::with_capacity((centers.len() - 1) * samples_per_segment + 1);
+    for index in 0..centers.len() - 1 {
+        let p0 = centers[index.saturating_sub(1)];
+        let p1 = centers[index];
+        let p2 = centers[index + 1];
+        let p3 = centers[(index + 2).min(centers.len() - 1)];
+
+        for sample in 0..samples_per_segment {
+            let t = sample as f32 / samples_per_segment as f32;
+            samples.push((
+                catmull_rom(p0.0, p1.0, p2.0, p3.0, t),
+                catmull_rom(p0.1, p1.1, p2.1, p3.1, t),
+            ));
+        }
+    }
+    samples.push(last);
+
+    samples
+}
+
+/// Uniform Catmull-Rom interpolation between `p1` and `p2`, for `t` between 0 and 1.
+fn catmull_rom(p0: f32, p1: f32, p2: f32, p3: f32, t: f32) -> f32 {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    0.5 * (2. * p1
+        + (p2 - p0) * t
+        + (2. * p0 - 5. * p1 + 4. * p2 - p3) * t2
+        + (3. * p1 - p0 - 3. * p2 + p3) * t3)
+}
+
+/// Turn a path into the directions of its steps, for instance to animate a unit or replay
+/// its moves. Returns one direction per step: `path.len() - 1` directions, or none if the path
+/// has less than two points.
+///
+/// # Arguments
+///
+/// * `path` - The path to follow, for instance the result of [`astar_path_fourwaygrid`].
+///
+/// # Panics
+///
+/// Panics if two consecutive points of the path are not adjacent, diagonals included.
+///
+/// # Examples
+/// ```
+/// use torchbearer::{path::path_to_directions, Direction};
+///
+/// let path = [(0, 2), (0, 1), (1, 1), (2, 0)];
+///
+/// assert_eq!(
+///     path_to_directions(&path),
+///     [Direction::North, Direction::East, Direction::NorthEast]
+/// );
+/// ```
+pub fn path_to_directions(path: &[Point]) -> Vec<Direction> {
+    path.windows(2)
+        .map(|step| {
+            let ((x0, y0), (x1, y1)) = (step[0], step[1]);
+            Direction::from_offset((x1 - x0, y1 - y0)).unwrap_or_else(|| {
+                panic!(
+                    "consecutive points of path should be adjacent, got ({}, {}) then ({}, {}).",
+                    x0, y0, x1, y1
+                )
+            })
+        })
+        .collect()
+}
+
+/// Find the walkable tile closest to `from`, for instance to snap a click on a wall to a
+/// valid destination before looking for a path. Returns `from` itself if it is walkable, and
+/// `None` if no tile of the map is walkable.
+///
+/// Distances are euclidean, as the crow flies: walls in between are ignored. Among equally close
+/// tiles, the one with the lowest index `x + y * width` is picked.
+///
+/// # Arguments
+///
+/// * `map` - a struct implementing the `PathMap` trait.
+/// * `from` - the position to start from.
+///
+/// # Panics
+///
+/// Panics if `from` is out of bounds of the map.
+///
+/// # Examples
+/// ```
+/// use torchbearer::{
+///     map::{grid_map, CellMeaning},
+///     path::nearest_walkable,
+/// };
+///
+/// let walls = vec![
+///     true, true, true, true,
+///     true, true, true, false,
+///     true, true, true, true,
+/// ];
+/// let map = grid_map(4, 3, walls, CellMeaning::Blocked);
+///
+/// assert_eq!(nearest_walkable(&map, (0, 0)), Some((3, 1)));
+/// ```
+pub fn nearest_walkable<T: PathMap>(map: &T, from: Point) -> Option<Point> {
+    assert_point_in_bounds(map, from);
+
+    let (width, height) = map.dimensions();
+    let (x, y) = from;
+    // The closest candidate, as its squared distance then its index.
+    let mut best: Option<(i32, i32)> = None;
+
+    // Tiles are visited ring by ring, each ring being a square one tile further away.
+    for (nx, ny) in Rings::new(from, width.max(height)) {
+        let (dx, dy) = (nx - x, ny - y);
+        let radius = dx.abs().max(dy.abs());
+        if best.is_some_and(|(distance, _)| distance < radius * radius) {
+            // Every tile left is further away than the best candidate.
+            break;
+        }
+        if nx < 0 || ny < 0 || nx >= width || ny >= height || !map.is_walkable((nx, ny)) {
+            continue;
+        }
+
+        let candidate = (dx * dx + dy * dy, nx + ny * width);
+        if best.is_none_or(|best| candidate < best) {
+            best = Some(candidate);
+        }
+    }
+
+    best.map(|(_, index)| (index % width, index / width))
+}
+
+/// Label the walkable regions of a map: two tiles get the same label if one can walk from one
+/// to the other, moving four way. Non walkable tiles are labelled `-1`.
+///
+/// Labels are deterministic: the map is scanned in row-major order (by index `x + y * width`),
+/// and each new region gets the next label. Region `0` is the one containing the walkable tile
+/// with the lowest index, region `1` contains the lowest indexed walkable tile not in region `0`,
+/// and so on.
+///
+/// Returns a vector with a label per tile, indexed by `x + y * width`.
+pub fn connected_regions<T: PathMap>(map: &T) -> Vec<i32> {
+    let (width, height) = map.dimensions();
+    let mut labels = vec![-1; (width * height) as usize];
+    let mut next_label = 0;
+    let mut stack = vec![];
+
+    for index in 0..labels.len() {
+        let (x, y) = (index as i32 % width, index as i32 / width);
+        if labels[index] != -1 || !map.is_walkable((x, y)) {
+            continue;
+        }
+
+        flood(map, (x, y), &mut stack, |index| {
+            if labels[index] == -1 {
+                labels[index] = next_label;
+                true
+            } else {
+                false
+            }
+        });
+        next_label += 1;
+    }
+
+    labels
+}
+
+/// The component of the non walkable tiles, in the result of [`connected_components`].
+pub const NO_COMPONENT: u32 = u32::MAX;
+
+/// Same as [`connected_regions`], with unsigned labels: non walkable tiles are labelled
+/// [`NO_COMPONENT`].
+///
+/// Two walkable tiles are connected if their labels are equal, which is what
+/// [`RegionMap::same_region`] checks.
+///
+/// # Examples
+/// ```
+/// use torchbearer::{
+///     map::{grid_map, CellMeaning},
+///     path::{connected_components, NO_COMPONENT},
+/// };
+///
+/// let map = grid_map(3, 1, vec![true, false, true], CellMeaning::Open);
+///
+/// assert_eq!(connected_components(&map), [0, NO_COMPONENT, 1]);
+/// ```
+pub fn connected_components<T: PathMap>(map: &T) -> Vec<u32> {
+    connected_regions(map)
+        .into_iter()
+        .map(|label| {
+            if label == -1 {
+                NO_COMPONENT
+            } else {
+                label as u32
+            }
+        })
+        .collect()
+}
+
+/// Find every walkable tile that can be reached from `start`, moving four way, like the paint
+/// bucket of a drawing tool. Empty if `start` itself is not walkable.
+///
+/// Returns the positions in row-major order, including `start`.
+///
+/// # Arguments
+///
+/// * `map` - a struct implementing the `PathMap` trait.
+/// * `start` - the position to fill from.
+///
+/// # Panics
+///
+/// Panics if `start` is out of the map bounds.
+pub fn flood_fill<T: PathMap>(map: &T, start: Point) -> Vec<Point> {
+    assert_point_in_bounds(map, start);
+    if !map.is_walkable(start) {
+        return vec![];
+    }
+
+    let (width, height) = map.dimensions();
+    let mut filled = vec![false; (width * height) as usize];
+    flood(map, start, &mut vec![], |index| {
+        !std::mem::replace(&mut filled[index], true)
+    });
+
+    filled
+        .into_iter()
+        .enumerate()
+        .filter(|&(_, is_filled)| is_filled)
+        .map(|(index, _)| (index as i32 % width, index as i32 / width))
+        .collect()
+}
+
+/// Visit the walkable tiles connected to the walkable tile `start`, moving four way, using an
+/// explicit stack rather than recursion. `claim` is called with the index of each tile met, and
+/// returns `true` the first time it's called for a tile: only then are its neighboors visited.
+fn flood<T, F>(map: &T, start: Point, stack: &mut Vec<Point>, mut claim: F)
+where
+    T: PathMap,
+    F: FnMut(usize) -> bool,
+{
+    let (width, height) = map.dimensions();
+    if !claim((start.0 + start.1 * width) as usize) {
+        return;
+    }
+
+    stack.push(start);
+    while let Some((x, y)) = stack.pop() {
+        for (nx, ny) in [(x, y + 1), (x, y - 1), (x - 1, y), (x + 1, y)] {
+            if nx < 0 || ny < 0 || nx >= width || ny >= height {
+                continue;
+            }
+            if map.is_walkable((nx, ny)) && claim((nx + ny * width) as usize) {
+                stack.push((nx, ny));
+            }
+        }
+    }
+}
+
+/// Precomputed [`connected_regions`] of a map, to check in constant time if two tiles are
+/// connected. On a static map, this is far cheaper than running a search for each query.
+///
+/// # Examples
+/// ```
+/// use torchbearer::map::{grid_map, CellMeaning};
+/// use torchbearer::path::RegionMap;
+///
+/// // A wall splitting the map in two.
+/// let walls = vec![
+///     false, true, false, //
+///     false, true, false, //
+/// ];
+/// let map = grid_map(3, 2, walls, CellMeaning::Blocked);
+/// let regions = RegionMap::new(&map);
+///
+/// assert!(regions.same_region((0, 0), (0, 1)));
+/// assert!(!regions.same_region((0, 0), (2, 0)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RegionMap {
+    width: i32,
+    height: i32,
+    labels: Vec<i32>,
+}
+
+impl RegionMap {
+    /// Label the regions of `map`. The map is read once: create a new `RegionMap` if it changes.
+    pub fn new<T: PathMap>(map: &T) -> Self {
+        let (width, height) = map.dimensions();
+        RegionMap {
+            width,
+            height,
+            labels: connected_regions(map),
+        }
+    }
+
+    /// The label of the region containing `point`, as defined by [`connected_regions`].
+    /// `None` if the tile is not walkable, or out of the map.
+    pub fn region_of(&self, (x, y): Point) -> Option<i32> {
+        if x < 0 || y < 0 || x >= self.width || y >= self.height {
+            return None;
+        }
+        match self.labels[(x + y * self.width) as usize] {
+            -1 => None,
+            label => Some(label),
+        }
+    }
+
+    /// Is it possible to walk from `a` to `b`. Always `false` if one of them isn't walkable.
+    pub fn same_region(&self, a: Point, b: Point) -> bool {
+        match (self.region_of(a), self.region_of(b)) {
+            (Some(region_a), Some(region_b)) => region_a == region_b,
+            _ => false,
+        }
+    }
+}
+
+/// Find the tiles that are both reachable within a movement budget and visible from `from`.
+/// Handy for abilities such as "hit any tile within 5 moves that you can see".
+///
+/// Movement is four way, each step costing the [`PathMap::cost`] of the tile it goes into.
+/// Visibility is computed with [`field_of_view`].
+///
+/// # Arguments
+///
+/// * `map` - a struct implementing both the `PathMap` and `VisionMap` traits.
+/// * `from` - the origin.
+/// * `move_budget` - the maximum cost of the path to a tile.
+/// * `sight_radius` - how far the vision should go.
+///
+/// # Panics
+///
+/// Panics if `from` is out of bounds of the map.
+pub fn targetable_tiles<T: PathMap + VisionMap>(
+    map: &T,
+    from: Point,
+    move_budget: f32,
+    sight_radius: i32,
+) -> Vec<Point> {
+    let (width, _) = PathMap::dimensions(map);
+    let visibles = field_of_view(map, from, sight_radius);
+    let reachable = reachable_within(map, from, move_budget);
+
+    visibles
+        .into_iter()
+        .filter(|&(x, y)| reachable[(x + y * width) as usize])
+        .collect()
+}
+
+/// Cost bounded Dijkstra expansion on a four way grid. Returns, for each tile of the map,
+/// wether it can be reached within `max_cost`.
+fn reachable_within<T: PathMap>(map: &T, from: Point, max_cost: f32) -> Vec<bool> {
+    let (width, height) = map.dimensions();
+    let from_index = (from.0 + from.1 * width) as usize;
+
+    let costs = bounded_dijkstra(
+        (width * height) as usize,
+        &[from_index],
+        max_cost,
+        |current, steps| {
+            let (x, y) = (current as i32 % width, current as i32 / width);
+            for (next_x, next_y) in [(x, y + 1), (x, y - 1), (x - 1, y), (x + 1, y)] {
+                if next_x >= 0
+                    && next_y >= 0
+                    && next_x < width
+                    && next_y < height
+                    && map.is_walkable((next_x, next_y))
+                {
+                    let next_index = (next_x + next_y * width) as usize;
+                    steps.push((next_index, map.cost((next_x, next_y))));
+                }
+            }
+        },
+    );
+
+    costs.into_iter().map(|cost| cost.is_some()).collect()
+}
+
+/// Find a path to the closest explored tile from which unexplored tiles can be reached, to
+/// drive an auto-explore.
+///
+/// Starting from `from`, tiles are visited in order of distance, moving four way through
+/// walkable tiles. The first one with a walkable but unexplored neighboor is the destination.
+/// The returned path contains `from` and the destination. Returns `None` once every reachable
+/// tile is explored.
+///
+/// # Arguments
+///
+/// * `map` - a struct implementing the `PathMap` trait.
+/// * `from` - the origin.
+/// * `explored` - which tiles were explored already.
+///
+/// # Panics
+///
+/// Panics if `from` is out of bounds of the map, or if `explored` doesn't have the same
+/// dimensions as the map.
+pub fn explore_step<T: PathMap>(map: &T, from: Point, explored: &BitGrid) -> Option<Vec<Point>> {
+    assert_point_in_bounds(map, from);
+    let (width, height) = map.dimensions();
+    if explored.dimensions() != (width, height) {
+        let (explored_width, explored_height) = explored.dimensions();
+        panic!(
+            "explored should have the dimensions of the map ({}, {}), got ({}, {}).",
+            width, height, explored_width, explored_height
+        );
+    }
+
+    let neighboors = |(x, y): Point| {
+        [(x, y + 1), (x, y - 1), (x - 1, y), (x + 1, y)]
+            .into_iter()
+            .filter(move |&(x, y)| {
+                x >= 0 && y >= 0 && x < width && y < height && map.is_walkable((x, y))
+            })
+    };
+
+    let mut came_from: Vec<Option<Point>> = vec![None; (width * height) as usize];
+    let mut visited = vec![false; (width * height) as usize];
+    visited[(from.0 + from.1 * width) as usize] = true;
+    let mut queue = VecDeque::from([from]);
+
+    while let Some(current) = queue.pop_front() {
+        if neighboors(current).any(|position| !explored.get(position)) {
+            let mut path = vec![current];
+            let mut position = current;
+            while let Some(previous) = came_from[(position.0 + position.1 * width) as usize] {
+                path.push(previous);
+                position = previous;
+            }
+            path.reverse();
+            return Some(path);
+        }
+
+        for next in neighboors(current) {
+            let next_index = (next.0 + next.1 * width) as usize;
+            if !visited[next_index] {
+                visited[next_index] = true;
+                came_from[next_index] = Some(current);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+fn assert_point_in_bounds<T: PathMap + ?Sized>(map: &T, position: Point) {
+    if let Err(error) = OutOfBounds::check(position, map.dimensions()) {
+        panic!("{}", error);
+    }
+}
+
+fn assert_index_in_bounds<T: Graph>(graph: &T, index: NodeId) {
+    if index >= graph.node_count() {
+        panic!(
+            "Index {} is out of bounds for a graph of size {}.",
+            index,
+            graph.node_count()
+        );
+    }
+}
+
+fn reconstruct_path(
+    from: NodeId,
+    to: NodeId,
+    came_from: Vec<Option<NodeId>>,
+    cost: f32,
+) -> Option<Vec<NodeId>> {
+    let mut current = Some(to);
+    let target_index = from;
+
+    let mut path = Vec::with_capacity((cost.floor() + 2.0) as usize);
+
+    while current != Some(target_index) {
+        if let Some(position) = current {
+            path.push(position);
+            current = if let Some(entry) = came_from[position] {
+                Some(entry)
+            } else {
+                return None;
+            }
+        } else {
+            return None;
+        }
+    }
+    path.push(target_index);
+    path.reverse();
+    Some(path)
+}
+
+struct State<C: PartialOrd, T> {
+    cost: C,
+    item: T,
+}
+impl<C: PartialOrd, T> PartialEq for State<C, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cost.eq(&other.cost)
+    }
+}
+
+impl<C: PartialOrd, T> Eq for State<C, T> {}
+
+// The priority queue depends on `Ord`.
+// Explicitly implement the trait so the queue becomes a min-heap
+// instead of a max-heap.
+impl<C: PartialOrd, T> Ord for State<C, T> {
+    fn cmp(&self, other: &State<C, T>) -> Ordering {
+        // Notice that the we flip the ordering on costs.
+        // In case of a tie we compare positions - this step is necessary
+        // to make implementations of `PartialEq` and `Ord` consistent.
+        other
+            .cost
+            .partial_cmp(&self.cost)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+// `PartialOrd` needs to be implemented as well.
+impl<C: PartialOrd, T> PartialOrd for State<C, T> {
+    fn partial_cmp(&self, other: &State<C, T>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// A graph for the A* algorithm. This is intended for a grid based representation, where each
+/// node would be a square on the map.
+pub trait Graph {
+    /// The amount of nodes in the graph, used to create correctly sized vectors.
+    fn node_count(&self) -> usize;
+
+    /// The cost between two points. A higher cost could represent a hard to cross terrain.
+    /// If normal terrain would cost 1 to go from a to be, climbing a mountain side could cost 2.
+    fn cost_between(&self, a: NodeId, b: NodeId) -> f32;
+
+    /// How close we are from our target.
+    /// See <https://www.redblobgames.com/pathfinding/a-star/introduction.html#greedy-best-first>
+    /// for more details about how it is useful.
+    fn heuristic(&self, a: NodeId, b: NodeId) -> f32;
+
+    /// From point a, where can you go. Create a list of all possible neighboors.
+    /// No need to filter the walkable ones, or the one in bounds: the algorithm
+    /// does it later for optimisation purposes.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - the position whose neighboors you are looking for.
+    /// * `into` - push the neighboors into this vector.
+    ///   No need to clear explicitely, as `clear()` is called before each call to this method.
+    fn neighboors(&self, a: NodeId, into: &mut Vec<NodeId>);
+}
+
+/// A wrapper around a Map, representing the graph for a four way grid type of Map, where
+/// it's possible to go north, east, south and west, but not in diagonal.
+pub struct FourWayGridGraph<'a, T: PathMap + ?Sized> {
+    map: &'a T,
+    width: i32,
+    height: i32,
+}
+
+impl<'a, T: PathMap + ?Sized> FourWayGridGraph<'a, T> {
+    pub fn new(map: &'a T) -> Self {
+        let (width, height) = map.dimensions();
+        FourWayGridGraph { map, width, height }
+    }
+
+    /// Is the node at position (x, y) walkable.
+    fn is_walkable(&self, x: i32, y: i32) -> bool {
+        self.map.is_walkable((x, y))
+    }
+
+    /// The `NodeId` of the tile at position `(x, y)`: `x + y * width`.
+    pub fn point_to_index(&self, (x, y): Point) -> NodeId {
+        (x + y * self.width) as usize
+    }
+
+    /// The position `(x, y)` of the tile with the given `NodeId`.
+    pub fn index_to_point(&self, index: NodeId) -> Point {
+        (index as i32 % self.width, index as i32 / self.width)
+    }
+}
+
+impl<'a, T: PathMap + ?Sized> Graph for FourWayGridGraph<'a, T> {
+    fn node_count(&self) -> usize {
+        (self.width * self.height) as usize
+    }
+
+    fn cost_between(&self, a: NodeId, b: NodeId) -> f32 {
+        let basic = 1.;
+        let (x1, y1) = self.index_to_point(a);
+        let (x2, y2) = self.index_to_point(b);
+        // Why the nudge? Check https://www.redblobgames.com/pathfinding/a-star/implementation.html#troubleshooting-ugly-path
+        // For a path in a 4 way grid, going up 3 times then left 3 times is the same cost as
+        // going up then left then up then... So we add a small nudge to the cost to make sure
+        // the algorithm doesn't follow straight path when it could go diagonal.
+        let nudge = if ((x1 + y1) % 2 == 0 && x2 != x1) || ((x1 + y1) % 2 == 1 && y2 != y1) {
+            1.
+        } else {
+            0.
+        };
+        (basic + 0.001 * nudge) * self.map.cost((x2, y2))
+    }
+
+    fn heuristic(&self, a: NodeId, b: NodeId) -> f32 {
+        let (xa, ya) = self.index_to_point(a);
+        let (xb, yb) = self.index_to_point(b);
+
+        ((xa - xb).abs() + (ya - yb).abs()) as f32
+    }
+
+    fn neighboors(&self, a: NodeId, into: &mut Vec<NodeId>) {
+        let (x, y) = self.index_to_point(a);
+
+        fn add_to_neighboors_if_qualified<'a, T: PathMap + ?Sized>(
+            graph: &FourWayGridGraph<'a, T>,
+            (x, y): Point,
+            into: &mut Vec<NodeId>,
+        ) {
+            if x < 0 || y < 0 || x >= graph.width || y >= graph.height || !graph.is_walkable(x, y) {
+                return;
+            }
+            into.push(graph.point_to_index((x, y)));
+        }
+
+        // South, north, west then east: the order the neighboors were always listed in, which
+        // decides between paths of equal cost.
+        for direction in [
+            Direction::South,
+            Direction::North,
+            Direction::West,
+            Direction::East,
+        ] {
+            add_to_neighboors_if_qualified(self, direction.apply((x, y)), into);
+        }
+    }
+}
+
+/// A wrapper around a Map, representing the graph for a four way grid type of Map that wraps
+/// around its edges, like a torus: going west from the first column leads to the last one,
+/// and going north from the first row leads to the last one.
+///
+/// The heuristic is the Manhattan distance, going across the edges when it is shorter.
+///
+/// # Examples
+/// ```
+/// use torchbearer::{
+///     map::Grid,
+///     path::{astar_path, WrappingGridGraph},
+/// };
+///
+/// let map = Grid::new(10, 5);
+/// let graph = WrappingGridGraph::new(&map);
+///
+/// let path = astar_path(&graph, graph.point_to_index((1, 2)), graph.point_to_index((8, 2)))
+///     .unwrap();
+/// let positions: Vec<_> = path.into_iter().map(|index| graph.index_to_point(index)).collect();
+///
+/// assert_eq!(positions, [(1, 2), (0, 2), (9, 2), (8, 2)]);
+/// ```
+pub struct WrappingGridGraph<'a, T: PathMap + ?Sized> {
+    map: &'a T,
+    width: i32,
+    height: i32,
+}
+
+impl<'a, T: PathMap + ?Sized> WrappingGridGraph<'a, T> {
+    pub fn new(map: &'a T) -> Self {
+        let (width, height) = map.dimensions();
+        WrappingGridGraph { map, width, height }
+    }
+
+    /// The `NodeId` of the tile at position `(x, y)`: `x + y * width`.
+    pub fn point_to_index(&self, (x, y): Point) -> NodeId {
+        (x + y * self.width) as usize
+    }
+
+    /// The position `(x, y)` of the tile with the given `NodeId`.
+    pub fn index_to_point(&self, index: NodeId) -> Point {
+        (index as i32 % self.width, index as i32 / self.width)
+    }
+}
+
+impl<'a, T: PathMap + ?Sized> Graph for WrappingGridGraph<'a, T> {
+    fn node_count(&self) -> usize {
+        (self.width * self.height) as usize
+    }
+
+    fn cost_between(&self, _a: NodeId, b: NodeId) -> f32 {
+        self.map.cost(self.index_to_point(b))
+    }
+
+    fn heuristic(&self, a: NodeId, b: NodeId) -> f32 {
+        let (xa, ya) = self.index_to_point(a);
+        let (xb, yb) = self.index_to_point(b);
+        let (dx, dy) = ((xa - xb).abs(), (ya - yb).abs());
+
+        (dx.min(self.width - dx) + dy.min(self.height - dy)) as f32
+    }
+
+    fn neighboors(&self, a: NodeId, into: &mut Vec<NodeId>) {
+        let (x, y) = self.index_to_point(a);
+
+        for direction in Direction::cardinals() {
+            let (nx, ny) = direction.apply((x, y));
+            let wrapped = (nx.rem_euclid(self.width), ny.rem_euclid(self.height));
+            if self.map.is_walkable(wrapped) {
+                into.push(self.point_to_index(wrapped));
+            }
+        }
+    }
+}
+
+/// A wrapper around a Map, representing the graph for an eight way grid type of Map, where
+/// it's possible to go north, east, south and west, as well as in diagonal.
+///
+/// Cardinal moves cost `1`, diagonal moves cost `√2`. Whether a diagonal move can go around
+/// wall corners is decided by a [`CornerPolicy`], [`CornerPolicy::RequireOne`] by default.
+pub struct EightWayGridGraph<'a, T: PathMap + ?Sized> {
+    map: &'a T,
+    width: i32,
+    height: i32,
+    corner_policy: CornerPolicy,
+}
+
+/// When can a diagonal move squeeze between the corners of the two orthogonal tiles it goes
+/// around.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CornerPolicy {
+    /// Diagonal moves are always possible, even between two walls.
+    Allow,
+    /// At least one of the two orthogonal tiles must be walkable. Most roguelikes work this way.
+    #[default]
+    RequireOne,
+    /// Both orthogonal tiles must be walkable, so nothing can slip through diagonal gaps.
+    RequireBoth,
+}
+
+impl<'a, T: PathMap + ?Sized> EightWayGridGraph<'a, T> {
+    pub fn new(map: &'a T) -> Self {
+        Self::with_corner_policy(map, CornerPolicy::default())
+    }
+
+    /// Creates the graph with a specific rule for cutting corners.
+    pub fn with_corner_policy(map: &'a T, corner_policy: CornerPolicy) -> Self {
+        let (width, height) = map.dimensions();
+        EightWayGridGraph {
+            map,
+            width,
+            height,
+            corner_policy,
+        }
+    }
+
+    /// Is the node at position (x, y) in the map, and walkable.
+    fn is_walkable(&self, x: i32, y: i32) -> bool {
+        x >= 0 && y >= 0 && x < self.width && y < self.height && self.map.is_walkable((x, y))
+    }
+
+    /// The `NodeId` of the tile at position `(x, y)`: `x + y * width`.
+    pub fn point_to_index(&self, (x, y): Point) -> NodeId {
+        (x + y * self.width) as usize
+    }
+
+    /// The position `(x, y)` of the tile with the given `NodeId`.
+    pub fn index_to_point(&self, index: NodeId) -> Point {
+        (index as i32 % self.width, index as i32 / self.width)
+    }
+}
+
+impl<'a, T: PathMap + ?Sized> Graph for EightWayGridGraph<'a, T> {
+    fn node_count(&self) -> usize {
+        (self.width * self.height) as usize
+    }
+
+    fn cost_between(&self, a: NodeId, b: NodeId) -> f32 {
+        let (x1, y1) = self.index_to_point(a);
+        let (x2, y2) = self.index_to_point(b);
+        let basic = if x1 != x2 && y1 != y2 {
+            std::f32::consts::SQRT_2
+        } else {
+            1.
+        };
+        basic * self.map.cost((x2, y2))
+    }
+
+    fn heuristic(&self, a: NodeId, b: NodeId) -> f32 {
+        let (xa, ya) = self.index_to_point(a);
+        let (xb, yb) = self.index_to_point(b);
+        let (dx, dy) = ((xa - xb).abs() as f32, (ya - yb).abs() as f32);
+
+        // Octile distance: go in diagonal as much as possible, then straight.
+        dx.max(dy) + (std::f32::consts::SQRT_2 - 1.) * dx.min(dy)
+    }
+
+    fn neighboors(&self, a: NodeId, into: &mut Vec<NodeId>) {
+        let (x, y) = self.index_to_point(a);
+
+        for (dx, dy) in [(0, 1), (0, -1), (-1, 0), (1, 0)] {
+            if self.is_walkable(x + dx, y + dy) {
+                into.push(self.point_to_index((x + dx, y + dy)));
+            }
+        }
+
+        for (dx, dy) in [(1, 1), (1, -1), (-1, 1), (-1, -1)] {
+            if !self.is_walkable(x + dx, y + dy) {
+                continue;
+            }
+
+            let corners_allow = match self.corner_policy {
+                CornerPolicy::Allow => true,
+                CornerPolicy::RequireOne => {
+                    self.is_walkable(x + dx, y) || self.is_walkable(x, y + dy)
+                }
+                CornerPolicy::RequireBoth => {
+                    self.is_walkable(x + dx, y) && self.is_walkable(x, y + dy)
+                }
+            };
+            if corners_allow {
+                into.push(self.point_to_index((x + dx, y + dy)));
+            }
+        }
+    }
+}
+
+/// A four way grid graph where some tiles are linked together by portals, like staircases or
+/// teleporters. Going through a portal costs `portal_cost`, and works both ways.
+///
+/// The heuristic takes the portals into account, so that [`astar_path`] still finds the
+/// shortest path, going through one or several portals whenever it's shorter.
+///
+/// # Examples
+/// ```
+/// use torchbearer::{
+///     map::{grid_map, CellMeaning},
+///     path::{astar_path, PortalGridGraph},
+/// };
+///
+/// let map = grid_map(10, 1, vec![true; 10], CellMeaning::Open);
+/// let graph = PortalGridGraph::new(&map, vec![((0, 0), (9, 0))], 1.);
+///
+/// let path = astar_path(&graph, 0, 9).unwrap();
+/// assert_eq!(path, [0, 9]);
+/// ```
+pub struct PortalGridGraph<'a, T: PathMap + ?Sized> {
+    graph: FourWayGridGraph<'a, T>,
+    portals: Vec<(NodeId, NodeId)>,
+    portal_cost: f32,
+}
+
+impl<'a, T: PathMap + ?Sized> PortalGridGraph<'a, T> {
+    /// Creates the graph.
+    ///
+    /// # Arguments
+    ///
+    /// * `map` - a struct implementing the `PathMap` trait.
+    /// * `portals` - pairs of linked positions. Each portal can be used both ways, as long as
+    ///   both ends are walkable.
+    /// * `portal_cost` - the cost of going through a portal.
+    ///
+    /// # Panics
+    ///
+    /// Panics if one of the portal ends is out of bounds of the map.
+    pub fn new(map: &'a T, portals: Vec<(Point, Point)>, portal_cost: f32) -> Self {
+        let graph = FourWayGridGraph::new(map);
+        let portals = portals
+            .into_iter()
+            .map(|(a, b)| {
+                assert_point_in_bounds(map, a);
+                assert_point_in_bounds(map, b);
+                (graph.point_to_index(a), graph.point_to_index(b))
+            })
+            .collect();
+
+        PortalGridGraph {
+            graph,
+            portals,
+            portal_cost,
+        }
+    }
+
+    /// The `NodeId` of the tile at position `(x, y)`: `x + y * width`.
+    pub fn point_to_index(&self, position: Point) -> NodeId {
+        self.graph.point_to_index(position)
+    }
+
+    /// The position `(x, y)` of the tile with the given `NodeId`.
+    pub fn index_to_point(&self, index: NodeId) -> Point {
+        self.graph.index_to_point(index)
+    }
+
+    /// The other end of the portals starting at `a`.
+    fn portal_exits(&self, a: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.portals.iter().filter_map(move |&(start, end)| {
+            if start == a {
+                Some(end)
+            } else if end == a {
+                Some(start)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+impl<'a, T: PathMap + ?Sized> Graph for PortalGridGraph<'a, T> {
+    fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    fn cost_between(&self, a: NodeId, b: NodeId) -> f32 {
+        let (xa, ya) = self.index_to_point(a);
+        let (xb, yb) = self.index_to_point(b);
+        let adjacent = (xa - xb).abs() + (ya - yb).abs() == 1;
+        if self.portal_exits(a).any(|exit| exit == b) {
+            // Two adjacent tiles can be linked by a portal too: take the cheapest way.
+            if adjacent {
+                self.portal_cost.min(self.graph.cost_between(a, b))
+            } else {
+                self.portal_cost
+            }
+        } else {
+            self.graph.cost_between(a, b)
+        }
+    }
+
+    fn heuristic(&self, a: NodeId, b: NodeId) -> f32 {
+        // Walking straight there, or walking to the closest portal and going through it. As
+        // portals can be chained, nothing is assumed about where the portal leads: this keeps
+        // the estimate lower than the real cost, whatever the portals taken.
+        let closest_portal = self
+            .portals
+            .iter()
+            .flat_map(|&(start, end)| [start, end])
+            .map(|entrance| self.graph.heuristic(a, entrance))
+            .min_by(f32::total_cmp);
+
+        match closest_portal {
+            Some(distance) => self.graph.heuristic(a, b).min(distance + self.portal_cost),
+            None => self.graph.heuristic(a, b),
+        }
+    }
+
+    fn neighboors(&self, a: NodeId, into: &mut Vec<NodeId>) {
+        self.graph.neighboors(a, into);
+        for exit in self.portal_exits(a) {
+            if self.graph.map.is_walkable(self.index_to_point(exit)) {
+                into.push(exit);
+            }
+        }
+    }
+}
+
+/// A four way grid graph where changing direction costs more. Each tile is split in five
+/// nodes, one per direction it can be entered from, plus one for the start of the path.
+struct TurnPenaltyGridGraph<'a, T: PathMap> {
+    graph: FourWayGridGraph<'a, T>,
+    turn_penalty: f32,
+}
+
+impl<'a, T: PathMap> TurnPenaltyGridGraph<'a, T> {
+    /// The arrival of the start node, which wasn't entered from anywhere.
+    const START: usize = 4;
+
+    /// The node of the tile `tile`, entered going in the direction `arrival`: the index of
+    /// the direction in [`Direction::cardinals`], or [`Self::START`].
+    fn node(&self, tile: NodeId, arrival: usize) -> NodeId {
+        tile * 5 + arrival
+    }
+
+    fn tile(&self, node: NodeId) -> NodeId {
+        node / 5
+    }
+
+    fn arrival(&self, node: NodeId) -> usize {
+        node % 5
+    }
+}
+
+impl<'a, T: PathMap> Graph for TurnPenaltyGridGraph<'a, T> {
+    fn node_count(&self) -> usize {
+        self.graph.node_count() * 5
+    }
+
+    fn cost_between(&self, a: NodeId, b: NodeId) -> f32 {
+        let cost = self.graph.map.cost(self.graph.index_to_point(self.tile(b)));
+        let arrival = self.arrival(a);
+        if arrival != Self::START && arrival != self.arrival(b) {
+            cost + self.turn_penalty
+        } else {
+            cost
+        }
+    }
+
+    fn heuristic(&self, a: NodeId, b: NodeId) -> f32 {
+        self.graph.heuristic(self.tile(a), self.tile(b))
+    }
+
+    fn neighboors(&self, a: NodeId, into: &mut Vec<NodeId>) {
+        let (x, y) = self.graph.index_to_point(self.tile(a));
+        for (arrival, direction) in Direction::cardinals().enumerate() {
+            let (nx, ny) = direction.apply((x, y));
+            if nx < 0 || ny < 0 || nx >= self.graph.width || ny >= self.graph.height {
+                continue;
+            }
+            if self.graph.is_walkable(nx, ny) {
+                into.push(self.node(self.graph.point_to_index((nx, ny)), arrival));
+            }
+        }
+    }
+}
+
+/// A four way grid graph where tiles next to walls cost more.
+struct CenteredGridGraph<'a, T: PathMap> {
+    graph: FourWayGridGraph<'a, T>,
+    wall_proximity_penalty: f32,
+}
+
+impl<'a, T: PathMap> CenteredGridGraph<'a, T> {
+    /// How many of the eight tiles around `(x, y)` are blocked or out of the map.
+    fn blocked_neighboors(&self, (x, y): Point) -> usize {
+        let graph = &self.graph;
+        [
+            (-1, -1),
+            (0, -1),
+            (1, -1),
+            (-1, 0),
+            (1, 0),
+            (-1, 1),
+            (0, 1),
+            (1, 1),
+        ]
+        .into_iter()
+        .map(|(dx, dy)| (x + dx, y + dy))
+        .filter(|&(x, y)| {
+            x < 0 || y < 0 || x >= graph.width || y >= graph.height || !graph.is_walkable(x, y)
+        })
+        .count()
+    }
+}
+
+impl<'a, T: PathMap> Graph for CenteredGridGraph<'a, T> {
+    fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    fn cost_between(&self, a: NodeId, b: NodeId) -> f32 {
+        let blocked = self.blocked_neighboors(self.graph.index_to_point(b));
+        self.graph.cost_between(a, b) + self.wall_proximity_penalty * blocked as f32
+    }
+
+    fn heuristic(&self, a: NodeId, b: NodeId) -> f32 {
+        self.graph.heuristic(a, b)
+    }
+
+    fn neighboors(&self, a: NodeId, into: &mut Vec<NodeId>) {
+        self.graph.neighboors(a, into)
+    }
+}
+
+/// A four way grid graph only walking on tiles with enough clearance around them.
+struct ClearanceGridGraph<'a, T: PathMap> {
+    graph: FourWayGridGraph<'a, T>,
+    clearances: Vec<i32>,
+    clearance: i32,
+}
+
+impl<'a, T: PathMap> Graph for ClearanceGridGraph<'a, T> {
+    fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    fn cost_between(&self, a: NodeId, b: NodeId) -> f32 {
+        self.graph.cost_between(a, b)
+    }
+
+    fn heuristic(&self, a: NodeId, b: NodeId) -> f32 {
+        self.graph.heuristic(a, b)
+    }
+
+    fn neighboors(&self, a: NodeId, into: &mut Vec<NodeId>) {
+        self.graph.neighboors(a, into);
+        into.retain(|&node| self.clearances[node] >= self.clearance);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use crate::{
+        bresenham::BresenhamLine, fov::VisionMap, path::astar_path, Direction, OutOfBounds, Point,
+    };
+
+    use super::{
+        astar_all_shortest_paths, astar_bidirectional, astar_path_budget, astar_path_centered,
+        astar_path_fourwaygrid, astar_path_fourwaygrid_dyn, astar_path_into, astar_path_iter,
+        astar_path_limited, astar_path_or_closest, astar_path_through, astar_path_to,
+        astar_path_to_nearest, astar_path_turn_penalty, astar_path_weighted, astar_path_with,
+        astar_path_with_clearance, astar_path_with_cost, breadth_first, catmull_rom_path,
+        clearance_field, connected_components, connected_regions, descend_dijkstra,
+        descend_path_smoothed, dijkstra_map, explore_step, flood_fill, greedy_best_first,
+        influence_map, is_reachable, is_reachable_fourwaygrid, jps_path, nearest_walkable,
+        path_to_directions, reachable, smooth_path, targetable_tiles, theta_star,
+        try_astar_path_fourwaygrid, BitGrid, CornerPolicy, EightWayGridGraph, FourWayGridGraph,
+        Graph, NodeId, PathMap, Pathfinder, PortalGridGraph, RegionMap, SearchOutcome,
+        WrappingGridGraph, NO_COMPONENT,
+    };
+
+    struct SampleMap {
+        width: i32,
+        height: i32,
+        walkable: Vec<bool>,
+    }
+
+    impl SampleMap {
+        fn new(width: i32, height: i32) -> Self {
+            SampleMap {
+                width,
+                height,
+                walkable: vec![true; (width * height) as usize],
+            }
+        }
+
+        fn build_wall(&mut self, from: Point, to: Point) {
+            let bresenham = BresenhamLine::new(from, to);
+            for (x, y) in bresenham {
+                self.walkable[(x + y * self.width) as usize] = false;
+            }
+        }
+    }
+
+    impl PathMap for SampleMap {
+        fn dimensions(&self) -> (i32, i32) {
+            (self.width, self.height)
+        }
+
+        fn is_walkable(&self, (x, y): Point) -> bool {
+            self.walkable[(x + y * self.width) as usize]
+        }
+    }
+
+    impl VisionMap for SampleMap {
+        fn dimensions(&self) -> (i32, i32) {
+            (self.width, self.height)
+        }
+
+        fn is_transparent(&self, (x, y): Point) -> bool {
+            self.walkable[(x + y * self.width) as usize]
+        }
+    }
+
+    /// A four way open grid where every step costs exactly 1, without tie breaking nudge.
+    struct UniformGraph {
+        width: i32,
+        height: i32,
+    }
+
+    impl Graph for UniformGraph {
+        fn node_count(&self) -> usize {
+            (self.width * self.height) as usize
+        }
+
+        fn cost_between(&self, _a: NodeId, _b: NodeId) -> f32 {
+            1.
+        }
+
+        fn heuristic(&self, a: NodeId, b: NodeId) -> f32 {
+            let (xa, ya) = (a as i32 % self.width, a as i32 / self.width);
+            let (xb, yb) = (b as i32 % self.width, b as i32 / self.width);
+            ((xa - xb).abs() + (ya - yb).abs()) as f32
+        }
+
+        fn neighboors(&self, a: NodeId, into: &mut Vec<NodeId>) {
+            let (x, y) = (a as i32 % self.width, a as i32 / self.width);
+            for (x, y) in [(x, y + 1), (x, y - 1), (x - 1, y), (x + 1, y)] {
+                if x >= 0 && y >= 0 && x < self.width && y < self.height {
+                    into.push((x + y * self.width) as usize);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn astar_find_path() {
+        let mut map = SampleMap::new(10, 10);
+        map.build_wall((3, 3), (3, 6));
+        map.build_wall((0, 3), (3, 3));
+
+        let from = (0, 4);
+        let to = (5, 4);
+
+        let path = astar_path_fourwaygrid(&map, from, to);
+        assert!(path.is_some());
+
+        if let Some(path) = path {
+            assert_eq!(from, path[0]);
+            assert_eq!(to, path[path.len() - 1]);
+
+            assert_eq!(
+                path,
+                [
+                    (0, 4),
+                    (0, 5),
+                    (1, 5),
+                    (1, 6),
+                    (2, 6),
+                    (2, 7),
+                    (3, 7),
+                    (4, 7),
+                    (5, 7),
+                    (5, 6),
+                    (5, 5),
+                    (5, 4)
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn four_way_neighboors_order() {
+        let map = SampleMap::new(3, 3);
+        let graph = FourWayGridGraph::new(&map);
+        let mut neighboors = vec![];
+        graph.neighboors(4, &mut neighboors);
+
+        // South, north, west, east: A* relies on it to break ties.
+        assert_eq!(neighboors, [7, 1, 3, 5]);
+    }
+
+    #[test]
+    fn astar_no_path() {
+        let mut map = SampleMap::new(10, 10);
+        map.build_wall((3, 3), (3, 6));
+        map.build_wall((0, 3), (3, 3));
+        map.build_wall((0, 6), (3, 6));
+
+        let from = (0, 4);
+        let to = (5, 4);
+
+        let path = astar_path_fourwaygrid(&map, from, to);
+        assert!(path.is_none());
+    }
+
+    #[test]
+    fn astar_limited_gives_up_on_unreachable_target() {
+        let mut map = SampleMap::new(100, 100);
+        map.build_wall((50, 49), (52, 49));
+        map.build_wall((50, 51), (52, 51));
+        map.build_wall((50, 50), (50, 50));
+        map.build_wall((52, 50), (52, 50));
+        let graph = FourWayGridGraph::new(&map);
+
+        let from = 0;
+        let to = 51 + 50 * 100;
+
+        assert_eq!(
+            astar_path_limited(&graph, from, to, 10),
+            SearchOutcome::LimitReached
+        );
+        assert_eq!(
+            astar_path_limited(&graph, from, to, usize::MAX),
+            SearchOutcome::Unreachable
+        );
+    }
+
+    #[test]
+    fn astar_limited_into_path() {
+        let map = SampleMap::new(100, 100);
+        let graph = FourWayGridGraph::new(&map);
+
+        let gave_up = astar_path_limited(&graph, 0, 9999, 50);
+        assert_eq!(gave_up, SearchOutcome::LimitReached);
+        assert_eq!(gave_up.into_path(), None);
+
+        let found = astar_path_limited(&graph, 0, 9999, 1_000);
+        assert_eq!(found.into_path(), astar_path(&graph, 0, 9999));
+    }
+
+    #[test]
+    fn astar_limited_finds_path_within_limit() {
+        let mut map = SampleMap::new(10, 10);
+        map.build_wall((3, 3), (3, 6));
+        let graph = FourWayGridGraph::new(&map);
+
+        let from = 4 * 10;
+        let to = 5 + 4 * 10;
+
+        let expected = astar_path(&graph, from, to).unwrap();
+        assert_eq!(
+            astar_path_limited(&graph, from, to, 100),
+            SearchOutcome::Found(expected)
+        );
+        assert_eq!(
+            astar_path_limited(&graph, from, to, 2),
+            SearchOutcome::LimitReached
+        );
+    }
+
+    #[test]
+    fn astar_with_cost_sums_step_costs() {
+        let mut map = SampleMap::new(10, 10);
+        map.build_wall((3, 0), (3, 6));
+        let graph = EightWayGridGraph::new(&map);
+
+        let (path, cost) = astar_path_with_cost(&graph, 0, 5).unwrap();
+        let expected: f32 = path
+            .windows(2)
+            .map(|step| graph.cost_between(step[0], step[1]))
+            .sum();
+
+        assert_eq!(Some(path), astar_path(&graph, 0, 5));
+        assert!((cost - expected).abs() < 1e-4);
+        assert!(astar_path_with_cost(&graph, 0, 0).unwrap().1 == 0.);
+    }
+
+    #[test]
+    fn pathfinder_matches_astar_path() {
+        let mut map = SampleMap::new(10, 10);
+        map.build_wall((3, 3), (3, 6));
+        map.build_wall((0, 3), (3, 3));
+        map.build_wall((6, 0), (6, 8));
+        let graph = FourWayGridGraph::new(&map);
+        let mut pathfinder = Pathfinder::new(10);
+
+        for (from, to) in [(40, 45), (0, 99), (40, 45), (99, 0), (33, 0), (12, 12)] {
+            assert_eq!(
+                pathfinder.find(&graph, from, to),
+                astar_path(&graph, from, to)
+            );
+        }
+    }
+
+    #[test]
+    fn astar_into_reuses_buffer() {
+        let mut map = SampleMap::new(10, 10);
+        map.build_wall((3, 3), (3, 6));
+        map.build_wall((0, 3), (3, 3));
+        let graph = FourWayGridGraph::new(&map);
+        let mut out = vec![42, 42];
+
+        assert!(astar_path_into(&graph, 40, 45, &mut out));
+        assert_eq!(Some(out.clone()), astar_path(&graph, 40, 45));
+
+        let mut pathfinder = Pathfinder::new(100);
+        assert!(pathfinder.find_into(&graph, 0, 99, &mut out));
+        assert_eq!(Some(out.clone()), astar_path(&graph, 0, 99));
+
+        map.build_wall((0, 6), (3, 6));
+        let graph = FourWayGridGraph::new(&map);
+        assert!(!astar_path_into(&graph, 40, 45, &mut out));
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn pathfinder_survives_generation_wrap() {
+        let map = SampleMap::new(5, 5);
+        let graph = FourWayGridGraph::new(&map);
+        let mut pathfinder = Pathfinder::new(25);
+        pathfinder.generation = u32::MAX - 1;
+
+        for _ in 0..3 {
+            assert_eq!(pathfinder.find(&graph, 0, 24), astar_path(&graph, 0, 24));
+        }
+    }
+
+    /// A map where the middle columns are a swamp, costly to walk through, except on one road.
+    struct SwampMap {
+        map: SampleMap,
+    }
+
+    impl PathMap for SwampMap {
+        fn dimensions(&self) -> (i32, i32) {
+            PathMap::dimensions(&self.map)
+        }
+
+        fn is_walkable(&self, position: Point) -> bool {
+            self.map.is_walkable(position)
+        }
+
+        fn cost(&self, (x, y): Point) -> f32 {
+            if (3..7).contains(&x) && y != 8 {
+                5.
+            } else {
+                1.
+            }
+        }
+    }
+
+    #[test]
+    fn astar_avoids_costly_tiles() {
+        let map = SwampMap {
+            map: SampleMap::new(10, 10),
+        };
+
+        let path = astar_path_fourwaygrid(&map, (0, 2), (9, 2)).unwrap();
+        assert!(path.contains(&(5, 8)));
+
+        // Without the swamp, straight line.
+        let path = astar_path_fourwaygrid(&map.map, (0, 2), (9, 2)).unwrap();
+        assert_eq!(path.len(), 10);
+    }
+
+    fn path_length(path: &[Point]) -> f32 {
+        path.windows(2)
+            .map(|step| {
+                if step[0].0 != step[1].0 && step[0].1 != step[1].1 {
+                    std::f32::consts::SQRT_2
+                } else {
+                    1.
+                }
+            })
+            .sum()
+    }
+
+    #[test]
+    fn jps_straight_shot_on_empty_map() {
+        let map = SampleMap::new(100, 100);
+
+        let path = jps_path(&map, (0, 0), (99, 99)).unwrap();
+        assert_eq!(path.len(), 100);
+        assert_eq!(path[50], (50, 50));
+
+        let path = jps_path(&map, (0, 10), (99, 10)).unwrap();
+        assert_eq!(path.len(), 100);
+    }
+
+    #[test]
+    fn jps_optimal_around_wall() {
+        let mut map = SampleMap::new(20, 20);
+        map.build_wall((10, 2), (10, 17));
+        let graph = EightWayGridGraph::with_corner_policy(&map, CornerPolicy::RequireBoth);
+
+        let path = jps_path(&map, (2, 10), (17, 10)).unwrap();
+        let expected = astar_path(&graph, 2 + 10 * 20, 17 + 10 * 20).unwrap();
+
+        assert_eq!(path[0], (2, 10));
+        assert_eq!(path[path.len() - 1], (17, 10));
+        for step in path.windows(2) {
+            assert!(map.is_walkable(step[1]));
+            assert!((step[0].0 - step[1].0).abs() <= 1 && (step[0].1 - step[1].1).abs() <= 1);
+        }
+        let expected: Vec<Point> = expected
+            .into_iter()
+            .map(|i| (i as i32 % 20, i as i32 / 20))
+            .collect();
+        assert!((path_length(&path) - path_length(&expected)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn jps_matches_astar_on_random_maps() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..20 {
+            let mut map = SampleMap::new(24, 24);
+            for _ in 0..150 {
+                let (x, y) = (rng.gen_range(0..24), rng.gen_range(0..24));
+                map.walkable[(x + y * 24) as usize] = false;
+            }
+            map.walkable[0] = true;
+            map.walkable[24 * 24 - 1] = true;
+            let graph = EightWayGridGraph::with_corner_policy(&map, CornerPolicy::RequireBoth);
+
+            let jps = jps_path(&map, (0, 0), (23, 23));
+            let astar = astar_path(&graph, 0, 24 * 24 - 1);
+            assert_eq!(jps.is_some(), astar.is_some());
+            if let (Some(jps), Some(astar)) = (jps, astar) {
+                let astar: Vec<Point> = astar
+                    .into_iter()
+                    .map(|i| (i as i32 % 24, i as i32 / 24))
+                    .collect();
+                assert!((path_length(&jps) - path_length(&astar)).abs() < 1e-3);
+            }
+        }
+    }
+
+    #[test]
+    fn astar_to_nearest_skips_walled_off_goal() {
+        let mut map = SampleMap::new(10, 10);
+        // The closest goal, (2, 2), is walled off.
+        map.build_wall((1, 1), (3, 1));
+        map.build_wall((1, 3), (3, 3));
+        map.build_wall((1, 2), (1, 2));
+        map.build_wall((3, 2), (3, 2));
+        let graph = FourWayGridGraph::new(&map);
+
+        let from = 0;
+        let goals = [2 + 2 * 10, 9 + 9 * 10];
+        let path = astar_path_to_nearest(&graph, from, &goals).unwrap();
+
+        assert_eq!(path, astar_path(&graph, from, goals[1]).unwrap());
+    }
+
+    #[test]
+    fn astar_to_nearest_picks_closest() {
+        let map = SampleMap::new(10, 10);
+        let graph = FourWayGridGraph::new(&map);
+
+        let path = astar_path_to_nearest(&graph, 5 + 5 * 10, &[0, 9 + 6 * 10]).unwrap();
+
+        assert_eq!(path[path.len() - 1], 9 + 6 * 10);
+        assert_eq!(path.len(), 6);
+        assert_eq!(astar_path_to_nearest(&graph, 0, &[]), None);
+    }
+
+    #[test]
+    fn astar_budget_rejects_expensive_paths() {
+        let graph = UniformGraph {
+            width: 10,
+            height: 10,
+        };
+
+        // (0, 0) to (3, 3) costs 6.
+        let to = 3 + 3 * 10;
+        assert_eq!(
+            astar_path_budget(&graph, 0, to, 6.),
+            astar_path(&graph, 0, to)
+        );
+        assert_eq!(astar_path_budget(&graph, 0, to, 5.9), None);
+        assert_eq!(astar_path_budget(&graph, 0, 0, 0.), Some(vec![0]));
+    }
+
+    #[test]
+    fn astar_bidirectional_long_corridor() {
+        let mut map = SampleMap::new(30, 5);
+        // A snake shaped corridor.
+        map.build_wall((0, 1), (28, 1));
+        map.build_wall((1, 3), (29, 3));
+        let graph = FourWayGridGraph::new(&map);
+
+        let from = 0;
+        let to = 29 + 4 * 30;
+        let path = astar_bidirectional(&graph, from, to);
+
+        let astar = astar_path(&graph, from, to).unwrap();
+        let cost = |path: &[NodeId]| -> f32 {
+            path.windows(2)
+                .map(|step| graph.cost_between(step[0], step[1]))
+                .sum()
+        };
+
+        let path = path.unwrap();
+        assert_eq!(path.len(), 29 * 3 + 5);
+        assert_eq!(path[0], from);
+        assert_eq!(path[path.len() - 1], to);
+        assert!((cost(&path) - cost(&astar)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn astar_bidirectional_same_cost_as_astar() {
+        let mut map = SampleMap::new(20, 20);
+        map.build_wall((5, 0), (5, 15));
+        map.build_wall((10, 5), (10, 19));
+        map.build_wall((12, 8), (19, 8));
+        let graph = EightWayGridGraph::new(&map);
+
+        for (from, to) in [(0, 399), (21, 215), (399, 0), (45, 45)] {
+            let bidirectional = astar_bidirectional(&graph, from, to).unwrap();
+            let astar = astar_path(&graph, from, to).unwrap();
+            let cost = |path: &[NodeId]| -> f32 {
+                path.windows(2)
+                    .map(|step| graph.cost_between(step[0], step[1]))
+                    .sum()
+            };
+
+            assert_eq!(bidirectional[0], from);
+            assert_eq!(bidirectional[bidirectional.len() - 1], to);
+            assert!((cost(&bidirectional) - cost(&astar)).abs() < 1e-4);
+        }
+
+        let mut walled = SampleMap::new(5, 5);
+        walled.build_wall((2, 0), (2, 4));
+        let graph = FourWayGridGraph::new(&walled);
+        assert_eq!(astar_bidirectional(&graph, 0, 4), None);
+    }
+
+    #[test]
+    fn astar_bidirectional_random_maps_same_cost_as_astar() {
+        let mut rng = StdRng::seed_from_u64(11);
+        for _ in 0..30 {
+            let mut map = SampleMap::new(24, 24);
+            for _ in 0..150 {
+                let (x, y) = (rng.gen_range(0..24), rng.gen_range(0..24));
+                map.walkable[(x + y * 24) as usize] = false;
+            }
+            let from = rng.gen_range(0..24 * 24);
+            let to = rng.gen_range(0..24 * 24);
+            map.walkable[from] = true;
+            map.walkable[to] = true;
+
+            check_same_cost(&FourWayGridGraph::new(&map), from, to);
+            check_same_cost(&EightWayGridGraph::new(&map), from, to);
+        }
+
+        fn check_same_cost<G: Graph>(graph: &G, from: NodeId, to: NodeId) {
+            let cost = |path: &[NodeId]| -> f32 {
+                path.windows(2)
+                    .map(|step| graph.cost_between(step[0], step[1]))
+                    .sum()
+            };
+            match (
+                astar_bidirectional(graph, from, to),
+                astar_path(graph, from, to),
+            ) {
+                (Some(bidirectional), Some(astar)) => {
+                    assert!((cost(&bidirectional) - cost(&astar)).abs() < 1e-3);
+                }
+                (bidirectional, astar) => assert_eq!(bidirectional, astar),
+            }
+        }
+    }
+
+    #[test]
+    fn theta_star_open_room_is_a_straight_line() {
+        let map = SampleMap::new(20, 20);
+
+        assert_eq!(
+            theta_star(&map, (1, 2), (17, 11)),
+            Some(vec![(1, 2), (17, 11)])
+        );
+    }
+
+    #[test]
+    fn theta_star_goes_around_walls() {
+        let mut map = SampleMap::new(20, 20);
+        map.build_wall((10, 0), (10, 15));
+
+        let path = theta_star(&map, (2, 2), (17, 2)).unwrap();
+
+        assert_eq!(path[0], (2, 2));
+        assert_eq!(path[path.len() - 1], (17, 2));
+        assert!(path.len() < 6);
+        for segment in path.windows(2) {
+            assert!(BresenhamLine::new(segment[0], segment[1]).all(|p| map.is_walkable(p)));
+        }
+
+        map.build_wall((10, 16), (10, 19));
+        assert_eq!(theta_star(&map, (2, 2), (17, 2)), None);
+    }
+
+    #[test]
+    fn greedy_best_first_finds_a_path() {
+        let mut map = SampleMap::new(10, 10);
+        map.build_wall((3, 3), (3, 6));
+        map.build_wall((0, 3), (3, 3));
+        let graph = FourWayGridGraph::new(&map);
+
+        let from = 4 * 10;
+        let to = 5 + 4 * 10;
+        let path = greedy_best_first(&graph, from, to).unwrap();
+
+        assert_eq!(path[0], from);
+        assert_eq!(path[path.len() - 1], to);
+        for step in path.windows(2) {
+            let mut neighboors = vec![];
+            graph.neighboors(step[0], &mut neighboors);
+            assert!(neighboors.contains(&step[1]));
+        }
+
+        map.build_wall((0, 6), (3, 6));
+        let graph = FourWayGridGraph::new(&map);
+        assert_eq!(greedy_best_first(&graph, from, to), None);
+    }
+
+    #[test]
+    fn is_reachable_matches_astar() {
+        let mut map = SampleMap::new(10, 10);
+        map.build_wall((3, 3), (3, 6));
+        map.build_wall((0, 3), (3, 3));
+
+        assert!(is_reachable_fourwaygrid(&map, (0, 4), (5, 4)));
+        assert!(is_reachable_fourwaygrid(&map, (0, 4), (0, 4)));
+
+        map.build_wall((0, 6), (3, 6));
+        assert!(!is_reachable_fourwaygrid(&map, (0, 4), (5, 4)));
+
+        let graph = FourWayGridGraph::new(&map);
+        assert_eq!(
+            is_reachable(&graph, 4 * 10, 5 + 4 * 10),
+            astar_path(&graph, 4 * 10, 5 + 4 * 10).is_some()
+        );
+    }
+
+    #[test]
+    fn astar_through_waypoints() {
+        let graph = UniformGraph {
+            width: 10,
+            height: 1,
+        };
+
+        let path = astar_path_through(&graph, &[0, 4, 9]).unwrap();
+        assert_eq!(path, (0..10).collect::<Vec<_>>());
+
+        let path = astar_path_through(&graph, &[2, 5, 3]).unwrap();
+        assert_eq!(path, [2, 3, 4, 5, 4, 3]);
+
+        assert_eq!(astar_path_through(&graph, &[7]), Some(vec![7]));
+        assert_eq!(astar_path_through(&graph, &[]), None);
+    }
+
+    #[test]
+    fn astar_through_unreachable_leg() {
+        let mut map = SampleMap::new(5, 5);
+        map.build_wall((3, 0), (3, 4));
+        let graph = FourWayGridGraph::new(&map);
+
+        assert_eq!(astar_path_through(&graph, &[0, 20, 4]), None);
+        assert!(astar_path_through(&graph, &[0, 20, 2]).is_some());
+    }
+
+    #[test]
+    fn astar_with_custom_heuristic() {
+        let mut map = SampleMap::new(10, 10);
+        map.build_wall((3, 3), (3, 6));
+        map.build_wall((0, 3), (3, 3));
+        let graph = FourWayGridGraph::new(&map);
+        let (from, to) = (4 * 10, 5 + 4 * 10);
+
+        let default = astar_path_with(&graph, from, to, |a, b| graph.heuristic(a, b));
+        assert_eq!(default, astar_path(&graph, from, to));
+
+        let dijkstra = astar_path_with(&graph, from, to, |_, _| 0.).unwrap();
+        assert_eq!(dijkstra.len(), default.unwrap().len());
+    }
+
+    #[test]
+    fn astar_weighted_stays_within_bound() {
+        let mut map = SampleMap::new(20, 20);
+        map.build_wall((5, 0), (5, 15));
+        map.build_wall((10, 5), (10, 19));
+        map.build_wall((12, 8), (19, 8));
+        let graph = FourWayGridGraph::new(&map);
+        let (from, to) = (0, 19 + 19 * 20);
+
+        let optimal = astar_path(&graph, from, to).unwrap();
+        assert_eq!(
+            astar_path_weighted(&graph, from, to, 1.),
+            Some(optimal.clone())
+        );
+
+        let weighted = astar_path_weighted(&graph, from, to, 2.).unwrap();
+        assert_eq!(weighted[0], from);
+        assert_eq!(weighted[weighted.len() - 1], to);
+        assert!(weighted.len() as f32 <= optimal.len() as f32 * 2.);
+    }
+
+    #[test]
+    #[should_panic(expected = "epsilon should be at least 1.0, got 0.5.")]
+    fn astar_weighted_small_epsilon_panics() {
+        let map = SampleMap::new(10, 10);
+        let graph = FourWayGridGraph::new(&map);
+
+        astar_path_weighted(&graph, 0, 1, 0.5);
+    }
+
+    #[test]
+    fn astar_iter_matches_astar_path() {
+        let mut map = SampleMap::new(10, 10);
+        map.build_wall((3, 3), (3, 6));
+        map.build_wall((0, 3), (3, 3));
+        let graph = FourWayGridGraph::new(&map);
+
+        let iter = astar_path_iter(&graph, 40, 45).unwrap();
+        let expected = astar_path(&graph, 40, 45).unwrap();
+        assert_eq!(iter.len(), expected.len());
+        assert_eq!(iter.collect::<Vec<_>>(), expected);
+
+        assert_eq!(
+            astar_path_iter(&graph, 7, 7).unwrap().collect::<Vec<_>>(),
+            [7]
+        );
+
+        map.build_wall((0, 6), (3, 6));
+        let graph = FourWayGridGraph::new(&map);
+        assert!(astar_path_iter(&graph, 40, 45).is_none());
+    }
+
+    #[test]
+    fn astar_to_closest_matching_node() {
+        let mut map = SampleMap::new(10, 10);
+        map.build_wall((0, 3), (6, 3));
+        let graph = UniformGraph {
+            width: 10,
+            height: 10,
+        };
+        let walled = FourWayGridGraph::new(&map);
+        let doors = [9, 2 + 5 * 10, 7 + 7 * 10];
+
+        let path = astar_path_to(&graph, 0, |index| doors.contains(&index)).unwrap();
+        assert_eq!(path[path.len() - 1], 2 + 5 * 10);
+        assert_eq!(path.len(), 8);
+
+        // Without the door at (9, 0), and with the wall making (2, 5) further than (7, 7).
+        let path = astar_path_to(&walled, 0, |index| doors[1..].contains(&index)).unwrap();
+        assert_eq!(path[path.len() - 1], 7 + 7 * 10);
+
+        assert_eq!(astar_path_to(&walled, 0, |_| false), None);
+    }
+
+    #[test]
+    fn astar_or_closest_gets_next_to_walled_target() {
+        let mut map = SampleMap::new(10, 10);
+        map.build_wall((5, 0), (5, 9));
+        let graph = FourWayGridGraph::new(&map);
+
+        let path = astar_path_or_closest(&graph, 1 + 2 * 10, 8 + 4 * 10).unwrap();
+        assert_eq!(path[0], 1 + 2 * 10);
+        assert_eq!(path[path.len() - 1], 4 + 4 * 10);
+
+        // Reachable, same as astar_path.
+        assert_eq!(
+            astar_path_or_closest(&graph, 0, 4 + 9 * 10),
+            astar_path(&graph, 0, 4 + 9 * 10)
+        );
+
+        // Only an invalid origin gives no path at all.
+        assert_eq!(astar_path_or_closest(&graph, 100, 0), None);
+        assert_eq!(astar_path_or_closest(&graph, 0, 0), Some(vec![0]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Index 120 is out of bounds for a graph of size 100.")]
+    fn astar_path_out_of_bounds_index_panics() {
+        let map = SampleMap::new(10, 10);
+        let graph = FourWayGridGraph::new(&map);
+
+        astar_path(&graph, 0, 120);
+    }
+
+    #[test]
+    #[should_panic(expected = "(x, y) should be between (0,0) and (10, 10), got (0, 12).")]
+    fn astar_fourway_out_of_bounds_index_panics() {
+        let map = SampleMap::new(10, 10);
+
+        astar_path_fourwaygrid(&map, (0, 0), (0, 12));
+    }
+
+    #[test]
+    fn try_astar_fourway_out_of_bounds_is_error() {
+        let map = SampleMap::new(10, 10);
+
+        assert_eq!(
+            try_astar_path_fourwaygrid(&map, (-1, 0), (0, 12)),
+            Err(OutOfBounds {
+                position: (-1, 0),
+                dimensions: (10, 10),
+            })
+        );
+        assert_eq!(
+            try_astar_path_fourwaygrid(&map, (0, 0), (5, 7)),
+            Ok(astar_path_fourwaygrid(&map, (0, 0), (5, 7)))
+        );
+    }
+
+    #[test]
+    fn path_to_directions_staircase() {
+        let path = [(0, 3), (0, 2), (1, 2), (1, 1), (2, 1)];
+
+        assert_eq!(
+            path_to_directions(&path),
+            [
+                Direction::North,
+                Direction::East,
+                Direction::North,
+                Direction::East
+            ]
+        );
+        assert_eq!(path_to_directions(&path[..1]), []);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "consecutive points of path should be adjacent, got (1, 1) then (1, 3)."
+    )]
+    fn path_to_directions_gap_panics() {
+        path_to_directions(&[(0, 1), (1, 1), (1, 3)]);
+    }
+
+    #[test]
+    fn wrapping_path_goes_across_the_edges() {
+        let mut map = SampleMap::new(10, 10);
+        // A wall in the middle, that only matters when not wrapping.
+        map.build_wall((5, 0), (5, 9));
+        let graph = WrappingGridGraph::new(&map);
+
+        let path: Vec<Point> = astar_path(
+            &graph,
+            graph.point_to_index((3, 8)),
+            graph.point_to_index((7, 1)),
+        )
+        .unwrap()
+        .into_iter()
+        .map(|index| graph.index_to_point(index))
+        .collect();
+
+        // The wall forces 6 steps west through the vertical seam instead of 4 steps east,
+        // then 3 steps south through the horizontal seam instead of 7 steps north.
+        assert_eq!(path.len(), 10);
+        let steps: Vec<Point> = path
+            .windows(2)
+            .map(|step| ((step[0].0 - step[1].0).abs(), (step[0].1 - step[1].1).abs()))
+            .collect();
+        assert!(steps
+            .iter()
+            .all(|step| matches!(step, (1, 0) | (9, 0) | (0, 1) | (0, 9))));
+        assert!(steps.contains(&(9, 0)));
+        assert!(steps.contains(&(0, 9)));
+    }
+
+    #[test]
+    fn turn_penalty_prefers_straight_lines() {
+        let map = SampleMap::new(10, 10);
+
+        let turns = |path: &[Point]| {
+            path_to_directions(path)
+                .windows(2)
+                .filter(|directions| directions[0] != directions[1])
+                .count()
+        };
+
+        let zig_zag = astar_path_fourwaygrid(&map, (1, 1), (6, 6)).unwrap();
+        let straight = astar_path_turn_penalty(&map, (1, 1), (6, 6), 0.5).unwrap();
+
+        assert_eq!(straight.len(), zig_zag.len());
+        assert!(turns(&zig_zag) > 1);
+        assert_eq!(turns(&straight), 1);
+    }
+
+    #[test]
+    fn turn_penalty_around_wall() {
+        let mut map = SampleMap::new(10, 10);
+        map.build_wall((5, 0), (5, 8));
+
+        let path = astar_path_turn_penalty(&map, (0, 0), (9, 0), 2.).unwrap();
+
+        assert_eq!(path.len(), 28);
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&(9, 0)));
+        assert!(path.contains(&(5, 9)));
+        assert_eq!(
+            astar_path_turn_penalty(&map, (0, 0), (9, 0), 0.).map(|path| path.len()),
+            Some(28)
+        );
     }
 
-    /// Is the node at position (x, y) walkable.
-    fn is_walkable(&self, x: i32, y: i32) -> bool {
-        self.map.is_walkable((x, y))
+    #[test]
+    fn influence_map_decays_and_sums() {
+        let mut map = SampleMap::new(5, 5);
+        map.build_wall((2, 0), (2, 4));
+        let graph = FourWayGridGraph::new(&map);
+
+        let influence = influence_map(
+            &graph,
+            &[
+                (graph.point_to_index((0, 0)), 1.),
+                (graph.point_to_index((0, 2)), -1.),
+                (graph.point_to_index((4, 4)), 4.),
+            ],
+            0.5,
+        );
+
+        assert_eq!(influence[graph.point_to_index((0, 0))], 0.75);
+        assert_eq!(influence[graph.point_to_index((0, 1))], 0.);
+        assert_eq!(influence[graph.point_to_index((1, 2))], -0.375);
+        // Walls are never reached, and the wall stops the influence from crossing.
+        assert_eq!(influence[graph.point_to_index((2, 2))], 0.);
+        assert_eq!(influence[graph.point_to_index((3, 3))], 1.);
+        assert_eq!(influence[graph.point_to_index((3, 0))], 0.125);
     }
 
-    fn point_to_index(&self, (x, y): Point) -> usize {
-        (x + y * self.width) as usize
+    #[test]
+    fn connected_components_two_rooms() {
+        let mut map = SampleMap::new(7, 4);
+        map.build_wall((3, 0), (3, 3));
+        let components = connected_components(&map);
+
+        let component = |(x, y): Point| components[(x + y * 7) as usize];
+        assert_eq!(component((0, 0)), 0);
+        assert_eq!(component((2, 3)), 0);
+        assert_eq!(component((4, 0)), 1);
+        assert_eq!(component((6, 3)), 1);
+        assert_eq!(component((3, 2)), NO_COMPONENT);
     }
 
-    fn index_to_point(&self, index: usize) -> Point {
-        (index as i32 % self.width, index as i32 / self.width)
+    #[test]
+    fn nearest_walkable_picks_closest_then_lowest_index() {
+        let mut map = SampleMap::new(9, 9);
+        for y in 0..9 {
+            map.build_wall((0, y), (8, y));
+        }
+        assert_eq!(nearest_walkable(&map, (4, 4)), None);
+
+        map.walkable[8 + 4 * 9] = true;
+        map.walkable[4 + 8 * 9] = true;
+        map.walkable[7 + 7 * 9] = true;
+        // (8, 4) and (4, 8) are 4 tiles away, (7, 7) is 4.24 tiles away.
+        assert_eq!(nearest_walkable(&map, (4, 4)), Some((8, 4)));
+
+        map.walkable[6 + 6 * 9] = true;
+        assert_eq!(nearest_walkable(&map, (4, 4)), Some((6, 6)));
+        assert_eq!(nearest_walkable(&map, (6, 6)), Some((6, 6)));
     }
-}
 
-impl<'a, T: PathMap> Graph for FourWayGridGraph<'a, T> {
-    fn node_count(&self) -> usize {
-        (self.width * self.height) as usize
+    #[test]
+    fn astar_all_shortest_paths_open_grid() {
+        let graph = UniformGraph {
+            width: 5,
+            height: 5,
+        };
+        let from = 0;
+        let to = 2 + 2 * 5;
+
+        let paths = astar_all_shortest_paths(&graph, from, to, 100);
+
+        // Going 2 steps right and 2 steps down can be done in 6 different orders.
+        assert_eq!(paths.len(), 6);
+        for path in paths.iter() {
+            assert_eq!(path.len(), 5);
+            assert_eq!(path[0], from);
+            assert_eq!(path[4], to);
+            let cost: f32 = path
+                .windows(2)
+                .map(|step| graph.cost_between(step[0], step[1]))
+                .sum();
+            assert_eq!(cost, 4.);
+        }
+        for (index, path) in paths.iter().enumerate() {
+            assert!(!paths[index + 1..].contains(path));
+        }
     }
 
-    fn cost_between(&self, a: NodeId, b: NodeId) -> f32 {
-        let basic = 1.;
-        let (x1, y1) = self.index_to_point(a);
-        let (x2, y2) = self.index_to_point(b);
-        // Why the nudge? Check https://www.redblobgames.com/pathfinding/a-star/implementation.html#troubleshooting-ugly-path
-        // For a path in a 4 way grid, going up 3 times then left 3 times is the same cost as
-        // going up then left then up then... So we add a small nudge to the cost to make sure
-        // the algorithm doesn't follow straight path when it could go diagonal.
-        let nudge = if ((x1 + y1) % 2 == 0 && x2 != x1) || ((x1 + y1) % 2 == 1 && y2 != y1) {
-            1.
-        } else {
-            0.
+    #[test]
+    fn astar_all_shortest_paths_respects_limit() {
+        let graph = UniformGraph {
+            width: 5,
+            height: 5,
         };
-        basic + 0.001 * nudge
+
+        let paths = astar_all_shortest_paths(&graph, 0, 2 + 2 * 5, 2);
+
+        assert_eq!(paths.len(), 2);
     }
 
-    fn heuristic(&self, a: NodeId, b: NodeId) -> f32 {
-        let (xa, ya) = self.index_to_point(a);
-        let (xb, yb) = self.index_to_point(b);
+    #[test]
+    fn targetable_tiles_is_reachable_and_visible() {
+        let mut map = SampleMap::new(14, 14);
+        map.build_wall((3, 0), (3, 3));
 
-        ((xa - xb).abs() + (ya - yb).abs()) as f32
+        let targetable = targetable_tiles(&map, (1, 1), 10., 12);
+
+        // Visible and close.
+        assert!(targetable.contains(&(1, 5)));
+        // Reachable in 9 steps, but hidden behind the wall.
+        assert!(!targetable.contains(&(4, 1)));
+        // Visible, but 11 steps away.
+        assert!(!targetable.contains(&(1, 12)));
+        assert!(targetable
+            .iter()
+            .all(|&(x, y)| (x - 1).abs() + (y - 1).abs() <= 10));
     }
 
-    fn neighboors(&self, a: NodeId, into: &mut Vec<NodeId>) {
-        let (x, y) = self.index_to_point(a);
+    #[test]
+    fn astar_centered_avoids_walls() {
+        let mut map = SampleMap::new(12, 5);
+        map.build_wall((0, 0), (11, 0));
+        map.build_wall((0, 4), (11, 4));
 
-        fn add_to_neighboors_if_qualified<'a, T: PathMap>(
-            graph: &FourWayGridGraph<'a, T>,
-            (x, y): Point,
-            into: &mut Vec<NodeId>,
-        ) {
-            if x < 0 || y < 0 || x >= graph.width || y >= graph.height || !graph.is_walkable(x, y) {
-                return;
+        let from = (1, 1);
+        let to = (10, 1);
+
+        let hugging = astar_path_centered(&map, from, to, 0.).unwrap();
+        assert_eq!(
+            Some(hugging.clone()),
+            astar_path_fourwaygrid(&map, from, to)
+        );
+        assert!(hugging.iter().all(|&(_, y)| y == 1));
+
+        let centered = astar_path_centered(&map, from, to, 1.).unwrap();
+        assert_eq!(centered[0], from);
+        assert_eq!(centered[centered.len() - 1], to);
+        assert!(centered[2..centered.len() - 2].iter().all(|&(_, y)| y == 2));
+    }
+
+    #[test]
+    fn clearance_field_distances() {
+        let mut map = SampleMap::new(5, 5);
+        map.build_wall((0, 0), (0, 0));
+
+        let clearances = clearance_field(&map);
+
+        assert_eq!(
+            clearances,
+            [
+                -1, 0, 0, 0, 0, //
+                0, 0, 1, 1, 0, //
+                0, 1, 1, 1, 0, //
+                0, 1, 1, 1, 0, //
+                0, 0, 0, 0, 0, //
+            ]
+        );
+    }
+
+    #[test]
+    fn astar_with_clearance_avoids_narrow_gaps() {
+        let mut map = SampleMap::new(15, 15);
+        map.build_wall((7, 0), (7, 1));
+        map.build_wall((7, 3), (7, 8));
+        map.build_wall((7, 12), (7, 14));
+
+        let from = (3, 2);
+        let to = (11, 2);
+
+        let narrow = astar_path_with_clearance(&map, from, to, 0);
+        assert_eq!(narrow, astar_path_fourwaygrid(&map, from, to));
+        assert!(narrow.unwrap().contains(&(7, 2)));
+
+        let wide = astar_path_with_clearance(&map, from, to, 1).unwrap();
+        assert_eq!(wide[0], from);
+        assert_eq!(wide[wide.len() - 1], to);
+        assert!(!wide.contains(&(7, 2)));
+        assert!(wide.contains(&(7, 10)));
+
+        assert!(astar_path_with_clearance(&map, from, to, 2).is_none());
+    }
+
+    #[test]
+    fn astar_fourway_through_trait_object() {
+        let mut map = SampleMap::new(10, 10);
+        map.build_wall((3, 3), (3, 6));
+        map.build_wall((0, 3), (3, 3));
+        let from = (0, 4);
+        let to = (5, 4);
+        let expected = astar_path_fourwaygrid(&map, from, to);
+
+        let boxed: Box<dyn PathMap> = Box::new(map);
+        let path = astar_path_fourwaygrid_dyn(boxed.as_ref(), from, to);
+
+        assert!(path.is_some());
+        assert_eq!(path, expected);
+    }
+
+    /// Breadth first costs to `goal`, four way.
+    fn distances_to(map: &SampleMap, goal: Point) -> Vec<Option<f32>> {
+        let mut distances = vec![None; (map.width * map.height) as usize];
+        distances[(goal.0 + goal.1 * map.width) as usize] = Some(0.);
+        let mut queue = std::collections::VecDeque::from([goal]);
+        while let Some((x, y)) = queue.pop_front() {
+            let distance = distances[(x + y * map.width) as usize].unwrap();
+            for (nx, ny) in [(x, y + 1), (x, y - 1), (x - 1, y), (x + 1, y)] {
+                if nx < 0 || ny < 0 || nx >= map.width || ny >= map.height {
+                    continue;
+                }
+                let index = (nx + ny * map.width) as usize;
+                if map.walkable[index] && distances[index].is_none() {
+                    distances[index] = Some(distance + 1.);
+                    queue.push_back((nx, ny));
+                }
             }
-            into.push(graph.point_to_index((x, y)));
         }
+        distances
+    }
 
-        add_to_neighboors_if_qualified(self, (x, y + 1), into);
-        add_to_neighboors_if_qualified(self, (x, y - 1), into);
-        add_to_neighboors_if_qualified(self, (x - 1, y), into);
-        add_to_neighboors_if_qualified(self, (x + 1, y), into);
+    #[test]
+    fn descend_smoothed_has_fewer_waypoints() {
+        let mut map = SampleMap::new(12, 12);
+        map.build_wall((4, 2), (4, 8));
+        let goal = (10, 9);
+        let from = (1, 1);
+        let dijkstra = distances_to(&map, goal);
+
+        let raw = descend_dijkstra(&dijkstra, &map, from);
+        let smoothed = descend_path_smoothed(&dijkstra, &map, from);
+
+        assert_eq!(raw[raw.len() - 1], goal);
+        assert_eq!(smoothed[0], from);
+        assert_eq!(smoothed[smoothed.len() - 1], goal);
+        assert!(smoothed.len() < raw.len());
+        for step in smoothed.windows(2) {
+            assert!(BresenhamLine::new(step[0], step[1]).all(|position| map.is_walkable(position)));
+        }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::{bresenham::BresenhamLine, path::astar_path, Point};
+    #[test]
+    fn connected_regions_labels_are_stable() {
+        let mut map = SampleMap::new(5, 4);
+        map.build_wall((2, 0), (2, 3));
+        map.build_wall((3, 2), (4, 2));
+        map.build_wall((0, 0), (0, 0));
 
-    use super::{astar_path_fourwaygrid, FourWayGridGraph, PathMap};
+        let regions = connected_regions(&map);
 
-    struct SampleMap {
-        width: i32,
-        height: i32,
-        walkable: Vec<bool>,
+        assert_eq!(
+            regions,
+            [
+                -1, 0, -1, 1, 1, //
+                0, 0, -1, 1, 1, //
+                0, 0, -1, -1, -1, //
+                0, 0, -1, 2, 2, //
+            ]
+        );
     }
 
-    impl SampleMap {
-        fn new(width: i32, height: i32) -> Self {
-            SampleMap {
-                width,
-                height,
-                walkable: vec![true; (width * height) as usize],
+    #[test]
+    fn eightway_goes_in_diagonal() {
+        let map = SampleMap::new(10, 10);
+        let graph = EightWayGridGraph::new(&map);
+
+        let path = astar_path(&graph, 0, 4 + 4 * 10).unwrap();
+
+        assert_eq!(path, [0, 11, 22, 33, 44]);
+    }
+
+    #[test]
+    fn eightway_does_not_squeeze_between_corners() {
+        let mut map = SampleMap::new(4, 4);
+        // Two walls touching by their corners, blocking the diagonal from (1, 1) to (2, 2).
+        map.build_wall((2, 1), (2, 1));
+        map.build_wall((1, 2), (1, 2));
+        let graph = EightWayGridGraph::new(&map);
+
+        let mut neighboors = vec![];
+        graph.neighboors(1 + 4, &mut neighboors);
+
+        assert!(!neighboors.contains(&(2 + 2 * 4)));
+        assert!(neighboors.contains(&2));
+
+        // With a single wall, cutting the corner is fine.
+        let mut map = SampleMap::new(4, 4);
+        map.build_wall((2, 1), (2, 1));
+        let graph = EightWayGridGraph::new(&map);
+        neighboors.clear();
+        graph.neighboors(1 + 4, &mut neighboors);
+
+        assert!(neighboors.contains(&(2 + 2 * 4)));
+    }
+
+    #[test]
+    fn eightway_corner_policies() {
+        let mut map = SampleMap::new(4, 4);
+        // Two walls touching by their corners around the diagonal from (1, 1) to (2, 2).
+        map.build_wall((2, 1), (2, 1));
+        map.build_wall((1, 2), (1, 2));
+
+        let mut single_wall = SampleMap::new(4, 4);
+        single_wall.build_wall((2, 1), (2, 1));
+
+        let diagonal_allowed = |map: &SampleMap, policy: CornerPolicy| {
+            let graph = EightWayGridGraph::with_corner_policy(map, policy);
+            let mut neighboors = vec![];
+            graph.neighboors(1 + 4, &mut neighboors);
+            neighboors.contains(&(2 + 2 * 4))
+        };
+
+        assert!(diagonal_allowed(&map, CornerPolicy::Allow));
+        assert!(!diagonal_allowed(&map, CornerPolicy::RequireOne));
+        assert!(!diagonal_allowed(&map, CornerPolicy::RequireBoth));
+
+        assert!(diagonal_allowed(&single_wall, CornerPolicy::Allow));
+        assert!(diagonal_allowed(&single_wall, CornerPolicy::RequireOne));
+        assert!(!diagonal_allowed(&single_wall, CornerPolicy::RequireBoth));
+
+        assert!(diagonal_allowed(
+            &SampleMap::new(4, 4),
+            CornerPolicy::RequireBoth
+        ));
+    }
+
+    #[test]
+    fn dijkstra_map_matches_distances() {
+        let mut map = SampleMap::new(10, 10);
+        map.build_wall((3, 0), (3, 8));
+        map.build_wall((6, 2), (9, 2));
+        let graph = FourWayGridGraph::new(&map);
+
+        let goal = (8, 0);
+        let costs = dijkstra_map(&graph, &[8]);
+
+        // The four way grid adds a tiny nudge to each step, to break ties.
+        for (cost, distance) in costs.into_iter().zip(distances_to(&map, goal)) {
+            match (cost, distance) {
+                (Some(cost), Some(distance)) => assert!((cost - distance).abs() < 0.1),
+                (cost, distance) => assert_eq!(cost, distance),
             }
         }
+    }
 
-        fn build_wall(&mut self, from: Point, to: Point) {
-            let bresenham = BresenhamLine::new(from, to);
-            for (x, y) in bresenham {
-                self.walkable[(x + y * self.width) as usize] = false;
+    #[test]
+    fn dijkstra_map_uses_closest_goal() {
+        let graph = UniformGraph {
+            width: 10,
+            height: 1,
+        };
+
+        let costs = dijkstra_map(&graph, &[0, 9]);
+
+        assert_eq!(
+            costs,
+            [0., 1., 2., 3., 4., 4., 3., 2., 1., 0.]
+                .into_iter()
+                .map(Some)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn dijkstra_map_unreachable_is_none() {
+        let mut map = SampleMap::new(5, 5);
+        map.build_wall((2, 0), (2, 4));
+        let graph = FourWayGridGraph::new(&map);
+
+        let costs = dijkstra_map(&graph, &[0]);
+
+        assert!(costs[1].is_some());
+        assert_eq!(costs[2], None);
+        assert_eq!(costs[4], None);
+    }
+
+    #[test]
+    fn catmull_rom_goes_through_tile_centers() {
+        let points = [(0, 0), (3, 1), (4, 5), (8, 5)];
+        let samples = catmull_rom_path(&points, 5);
+
+        assert_eq!(samples.len(), 3 * 5 + 1);
+        for (index, &(x, y)) in points.iter().enumerate() {
+            let (sx, sy) = samples[index * 5];
+            assert!((sx - (x as f32 + 0.5)).abs() < 1e-5);
+            assert!((sy - (y as f32 + 0.5)).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn catmull_rom_short_paths() {
+        assert!(catmull_rom_path(&[], 4).is_empty());
+        assert_eq!(catmull_rom_path(&[(2, 3)], 4), [(2.5, 3.5)]);
+    }
+
+    #[test]
+    fn explore_step_heads_to_frontier() {
+        let mut map = SampleMap::new(10, 5);
+        map.build_wall((5, 0), (5, 3));
+        let mut explored = BitGrid::new(10, 5);
+        for x in 0..7 {
+            for y in 0..5 {
+                explored.set((x, y), true);
             }
         }
+
+        let path = explore_step(&map, (1, 1), &explored).unwrap();
+
+        assert_eq!(path[0], (1, 1));
+        assert_eq!(path[path.len() - 1], (6, 4));
+        assert_eq!(path.len(), 9);
     }
 
-    impl PathMap for SampleMap {
-        fn dimensions(&self) -> (i32, i32) {
-            (self.width, self.height)
+    #[test]
+    fn explore_step_done_when_all_reachable_explored() {
+        let mut map = SampleMap::new(10, 5);
+        map.build_wall((5, 0), (5, 4));
+        let mut explored = BitGrid::new(10, 5);
+        for x in 0..5 {
+            for y in 0..5 {
+                explored.set((x, y), true);
+            }
         }
 
-        fn is_walkable(&self, (x, y): Point) -> bool {
-            self.walkable[(x + y * self.width) as usize]
+        assert_eq!(explore_step(&map, (1, 1), &explored), None);
+    }
+
+    #[test]
+    fn smooth_path_keeps_ends_and_avoids_walls() {
+        let mut map = SampleMap::new(10, 10);
+        map.build_wall((4, 0), (4, 6));
+        let path = astar_path_fourwaygrid(&map, (1, 1), (8, 1)).unwrap();
+
+        let smoothed = smooth_path(&map, &path);
+
+        assert!(smoothed.len() < path.len());
+        assert_eq!(smoothed[0], (1, 1));
+        assert_eq!(smoothed[smoothed.len() - 1], (8, 1));
+        for segment in smoothed.windows(2) {
+            assert!(BresenhamLine::new(segment[0], segment[1]).all(|p| map.is_walkable(p)));
         }
     }
 
     #[test]
-    fn astar_find_path() {
+    fn smooth_path_straight_line_on_open_map() {
+        let map = SampleMap::new(10, 10);
+        let path = astar_path_fourwaygrid(&map, (0, 0), (6, 6)).unwrap();
+
+        assert_eq!(smooth_path(&map, &path), [(0, 0), (6, 6)]);
+        assert_eq!(smooth_path(&map, &path[..2]), path[..2]);
+    }
+
+    #[test]
+    fn reachable_within_budget() {
+        let graph = UniformGraph {
+            width: 10,
+            height: 10,
+        };
+
+        let tiles = reachable(&graph, 5 + 5 * 10, 2.);
+
+        // A diamond of radius 2.
+        assert_eq!(tiles.len(), 13);
+        assert!(tiles.contains(&(5 + 5 * 10, 0.)));
+        assert!(tiles.contains(&(7 + 5 * 10, 2.)));
+        assert!(tiles.contains(&(6 + 6 * 10, 2.)));
+        assert!(!tiles.iter().any(|&(index, _)| index == 7 + 6 * 10));
+    }
+
+    #[test]
+    fn reachable_goes_around_walls() {
+        let mut map = SampleMap::new(5, 5);
+        map.build_wall((1, 0), (1, 3));
+        let graph = FourWayGridGraph::new(&map);
+
+        let tiles = reachable(&graph, 0, 4.5);
+
+        assert_eq!(tiles.len(), 5);
+        assert!(tiles.iter().all(|&(index, _)| index % 5 == 0));
+    }
+
+    #[test]
+    fn breadth_first_counts_steps() {
         let mut map = SampleMap::new(10, 10);
-        map.build_wall((3, 3), (3, 6));
-        map.build_wall((0, 3), (3, 3));
+        map.build_wall((3, 0), (3, 8));
+        map.build_wall((6, 2), (9, 2));
+        map.build_wall((7, 5), (9, 5));
+        map.build_wall((7, 6), (7, 9));
+        let graph = EightWayGridGraph::new(&map);
+        let four_way = FourWayGridGraph::new(&map);
 
-        let from = (0, 4);
-        let to = (5, 4);
+        let expected: Vec<Option<u32>> = distances_to(&map, (0, 0))
+            .into_iter()
+            .map(|distance| distance.map(|distance| distance as u32))
+            .collect();
+        assert_eq!(breadth_first(&four_way, 0), expected);
 
-        let path = astar_path_fourwaygrid(&map, from, to);
-        assert!(path.is_some());
+        let steps = breadth_first(&graph, 0);
+        assert_eq!(steps[0], Some(0));
+        assert_eq!(steps[2 + 2 * 10], Some(2));
+        assert_eq!(steps[9 + 9 * 10], None);
+    }
 
-        if let Some(path) = path {
-            assert_eq!(from, path[0]);
-            assert_eq!(to, path[path.len() - 1]);
+    #[test]
+    fn grid_graphs_convert_points_and_indices() {
+        let map = SampleMap::new(7, 5);
+        let four_way = FourWayGridGraph::new(&map);
+        let eight_way = EightWayGridGraph::new(&map);
 
-            assert_eq!(
-                path,
-                [
-                    (0, 4),
-                    (0, 5),
-                    (1, 5),
-                    (1, 6),
-                    (2, 6),
-                    (2, 7),
-                    (3, 7),
-                    (4, 7),
-                    (5, 7),
-                    (5, 6),
-                    (5, 5),
-                    (5, 4)
-                ]
-            );
-        }
+        assert_eq!(four_way.point_to_index((3, 2)), 17);
+        assert_eq!(four_way.index_to_point(17), (3, 2));
+        assert_eq!(eight_way.point_to_index((6, 4)), 34);
+        assert_eq!(eight_way.index_to_point(34), (6, 4));
     }
 
     #[test]
-    fn astar_no_path() {
+    fn portal_shortcuts_across_wall() {
         let mut map = SampleMap::new(10, 10);
-        map.build_wall((3, 3), (3, 6));
-        map.build_wall((0, 3), (3, 3));
-        map.build_wall((0, 6), (3, 6));
+        map.build_wall((5, 0), (5, 8));
+        let graph = PortalGridGraph::new(&map, vec![((3, 1), (7, 1))], 1.);
 
-        let from = (0, 4);
-        let to = (5, 4);
+        let from = graph.point_to_index((2, 1));
+        let to = graph.point_to_index((8, 1));
+        let path = astar_path(&graph, from, to).unwrap();
 
-        let path = astar_path_fourwaygrid(&map, from, to);
-        assert!(path.is_none());
+        assert_eq!(path, [12, 13, 17, 18]);
+
+        // Too costly, better walk around.
+        let graph = PortalGridGraph::new(&map, vec![((3, 1), (7, 1))], 100.);
+        let path = astar_path(&graph, from, to);
+        assert_eq!(path, astar_path(&FourWayGridGraph::new(&map), from, to));
     }
 
     #[test]
-    #[should_panic(expected = "Index 120 is out of bounds for a graph of size 100.")]
-    fn astar_path_out_of_bounds_index_panics() {
-        let map = SampleMap::new(10, 10);
-        let graph = FourWayGridGraph::new(&map);
+    fn portal_chain_is_shortest() {
+        let map = SampleMap::new(40, 40);
+        let graph = PortalGridGraph::new(&map, vec![((0, 1), (30, 30)), ((31, 30), (10, 1))], 1.);
+        let path_cost = |path: &[NodeId]| -> f32 {
+            path.windows(2)
+                .map(|step| graph.cost_between(step[0], step[1]))
+                .sum()
+        };
 
-        astar_path(&graph, 0, 120);
+        let from = graph.point_to_index((0, 0));
+        let to = graph.point_to_index((10, 0));
+        let (path, cost) = astar_path_with_cost(&graph, from, to).unwrap();
+        let dijkstra = astar_path_with(&graph, from, to, |_, _| 0.).unwrap();
+
+        // Three steps and two portals, the steps costing a little more than 1 with the nudge.
+        assert!((cost - 5.).abs() < 0.01);
+        assert!((path_cost(&path) - path_cost(&dijkstra)).abs() < 1e-4);
+        assert!(path.contains(&graph.point_to_index((30, 30))));
     }
 
     #[test]
-    #[should_panic(expected = "(x, y) should be between (0,0) and (10, 10), got (0, 12).")]
-    fn astar_fourway_out_of_bounds_index_panics() {
-        let map = SampleMap::new(10, 10);
+    fn portal_between_adjacent_tiles_costs_portal_cost() {
+        let map = SampleMap::new(4, 1);
+        let graph = PortalGridGraph::new(&map, vec![((1, 0), (2, 0))], 0.5);
 
-        astar_path_fourwaygrid(&map, (0, 0), (0, 12));
+        assert_eq!(graph.cost_between(1, 2), 0.5);
+        assert_eq!(graph.cost_between(2, 1), 0.5);
+        assert!(graph.cost_between(0, 1) >= 1.);
+    }
+
+    #[test]
+    fn flood_fill_stays_on_its_side() {
+        let mut map = SampleMap::new(10, 10);
+        map.build_wall((4, 0), (4, 9));
+
+        let left = flood_fill(&map, (1, 1));
+        let right = flood_fill(&map, (8, 3));
+
+        assert_eq!(left.len(), 40);
+        assert_eq!(right.len(), 50);
+        assert!(left.iter().all(|&(x, _)| x < 4));
+        assert!(right.iter().all(|&(x, _)| x > 4));
+        assert_eq!(left[..5], [(0, 0), (1, 0), (2, 0), (3, 0), (0, 1)]);
+        assert!(flood_fill(&map, (4, 4)).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "(x, y) should be between (0,0) and (10, 10), got (10, 2).")]
+    fn flood_fill_out_of_bounds_panics() {
+        flood_fill(&SampleMap::new(10, 10), (10, 2));
+    }
+
+    #[test]
+    fn region_map_same_region() {
+        let mut map = SampleMap::new(10, 6);
+        map.build_wall((4, 0), (4, 5));
+        let regions = RegionMap::new(&map);
+
+        assert!(regions.same_region((0, 0), (3, 5)));
+        assert!(regions.same_region((5, 2), (9, 0)));
+        assert!(!regions.same_region((0, 0), (9, 0)));
+        assert_eq!(regions.region_of((4, 2)), None);
+        assert!(!regions.same_region((4, 2), (4, 2)));
+        assert_eq!(regions.region_of((10, 2)), None);
     }
 }